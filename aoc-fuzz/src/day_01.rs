@@ -0,0 +1,68 @@
+use day_01::{generate, part1, part2};
+
+use crate::text;
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+pub fn parse(data: &[u8]) {
+    let input = text(data);
+    let _ = part1::process(&input);
+    let _ = part2::process(&input);
+}
+
+// First and last digit of every line, `words` also counts spelled out digits
+fn reference(input: &str, words: bool) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let digits: Vec<u32> = (0..line.len())
+                .filter_map(|i| {
+                    let rest = &line[i..];
+                    let digit = rest.chars().next()?.to_digit(10);
+                    let word = WORDS
+                        .iter()
+                        .position(|word| words && rest.starts_with(word))
+                        .map(|n| n as u32 + 1);
+                    digit.or(word)
+                })
+                .collect();
+            digits[0] * 10 + digits[digits.len() - 1]
+        })
+        .sum()
+}
+
+// Generated inputs stay below this size, bigger ones only make the reference slow
+pub const MAX_SIZE: usize = 50;
+
+pub fn differential(size: usize, seed: u64) {
+    let input = generate::generate(size, seed);
+    assert_eq!(reference(&input, false), part1::process(&input).unwrap(), "{input}");
+    assert_eq!(reference(&input, true), part2::process(&input).unwrap(), "{input}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            parse(&data);
+        }
+
+        #[test]
+        fn never_panics_near_valid(
+            size in 0usize..20,
+            seed in any::<u64>(),
+            edits in prop::collection::vec(any::<(u16, u8)>(), 1..8),
+        ) {
+            parse(&crate::mutate(&day_01::generate::generate(size, seed), &edits));
+        }
+
+        #[test]
+        fn agrees_with_reference(size in 1..MAX_SIZE, seed in any::<u64>()) {
+            differential(size, seed);
+        }
+    }
+}
\ No newline at end of file
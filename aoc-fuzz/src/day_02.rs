@@ -0,0 +1,83 @@
+use day_02::{generate, part1, part2};
+
+use crate::text;
+
+pub fn parse(data: &[u8]) {
+    let input = text(data);
+    let _ = part1::process(&input);
+    let _ = part2::process(&input);
+}
+
+// (sum of the possible game ids, sum of the powers)
+fn reference(input: &str) -> (u64, u128) {
+    let (mut possible, mut power) = (0, 0);
+    for line in input.lines() {
+        let (game, rounds) = line.split_once(": ").unwrap();
+        let id: u32 = game.trim_start_matches("Game ").parse().unwrap();
+        let (mut red, mut green, mut blue) = (0, 0, 0);
+        for cubes in rounds.split("; ").flat_map(|round| round.split(", ")) {
+            let (count, color) = cubes.split_once(' ').unwrap();
+            let count: u32 = count.parse().unwrap();
+            match color {
+                "red" => red = red.max(count),
+                "green" => green = green.max(count),
+                _ => blue = blue.max(count),
+            }
+        }
+        if red <= 12 && green <= 13 && blue <= 14 {
+            possible += u64::from(id);
+        }
+        power += u128::from(red) * u128::from(green) * u128::from(blue);
+    }
+    (possible, power)
+}
+
+// Generated inputs stay below this size, bigger ones only make the reference slow
+pub const MAX_SIZE: usize = 50;
+
+// Also with counts big enough for the powers, or their sum, to overflow a u64
+pub fn differential(size: usize, seed: u64) {
+    for max_count in [20, 1 << 21, u32::MAX] {
+        let input = generate::generate_with_counts(size, seed, max_count);
+        let (possible, power) = reference(&input);
+        assert_eq!(possible, part1::process(&input).unwrap(), "{input}");
+        match u64::try_from(power) {
+            Ok(power) => assert_eq!(power, part2::process(&input).unwrap(), "{input}"),
+            Err(_) => assert!(part2::process(&input).is_err(), "{input}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn never_panics_on_large_ids() {
+        let input = b"Game 4294967295: 1 red\nGame 4294967295: 1 red\n";
+        parse(input);
+        assert_eq!(8_589_934_590u64, part1::process(&text(input)).unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            parse(&data);
+        }
+
+        #[test]
+        fn never_panics_near_valid(
+            size in 0usize..20,
+            seed in any::<u64>(),
+            edits in prop::collection::vec(any::<(u16, u8)>(), 1..8),
+        ) {
+            parse(&crate::mutate(&day_02::generate::generate(size, seed), &edits));
+        }
+
+        #[test]
+        fn agrees_with_reference(size in 1..MAX_SIZE, seed in any::<u64>()) {
+            differential(size, seed);
+        }
+    }
+}
\ No newline at end of file
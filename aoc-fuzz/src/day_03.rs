@@ -0,0 +1,104 @@
+use day_03::{generate, part1, part2};
+
+use crate::text;
+
+pub fn parse(data: &[u8]) {
+    let input = text(data);
+    let _ = part1::process(&input);
+    let _ = part2::process(&input);
+}
+
+// (row, first column, last column, value) of every number
+fn numbers(grid: &[Vec<u8>]) -> Vec<(usize, usize, usize, u64)> {
+    let mut numbers = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            if x > start {
+                let value = std::str::from_utf8(&row[start..x]).unwrap().parse().unwrap();
+                numbers.push((y, start, x - 1, value));
+            } else {
+                x += 1;
+            }
+        }
+    }
+    numbers
+}
+
+// Checks every cell around every number
+fn reference(input: &str) -> (u64, u64) {
+    let grid: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+    let numbers = numbers(&grid);
+    let touches = |&(y, first, last, _): &(usize, usize, usize, u64), (cy, cx): (usize, usize)| {
+        cy + 1 >= y && cy <= y + 1 && cx + 1 >= first && cx <= last + 1
+    };
+
+    let mut parts = 0;
+    let mut ratios = 0;
+    for (cy, row) in grid.iter().enumerate() {
+        for (cx, &cell) in row.iter().enumerate() {
+            if cell == b'*' {
+                let adjacent: Vec<u64> = numbers
+                    .iter()
+                    .filter(|number| touches(number, (cy, cx)))
+                    .map(|number| number.3)
+                    .collect();
+                if let [a, b] = adjacent[..] {
+                    ratios += a * b;
+                }
+            }
+        }
+    }
+    for number in &numbers {
+        let is_part = grid.iter().enumerate().any(|(cy, row)| {
+            row.iter().enumerate().any(|(cx, &cell)| {
+                cell != b'.' && !cell.is_ascii_digit() && touches(number, (cy, cx))
+            })
+        });
+        if is_part {
+            parts += number.3;
+        }
+    }
+    (parts, ratios)
+}
+
+// Generated inputs stay below this size, bigger ones only make the reference slow
+pub const MAX_SIZE: usize = 20;
+
+pub fn differential(size: usize, seed: u64) {
+    let input = generate::generate(size, seed);
+    let (parts, ratios) = reference(&input);
+    assert_eq!(parts, part1::process(&input).unwrap(), "{input}");
+    assert_eq!(ratios, part2::process(&input).unwrap(), "{input}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            parse(&data);
+        }
+
+        #[test]
+        fn never_panics_near_valid(
+            size in 0usize..20,
+            seed in any::<u64>(),
+            edits in prop::collection::vec(any::<(u16, u8)>(), 1..8),
+        ) {
+            parse(&crate::mutate(&day_03::generate::generate(size, seed), &edits));
+        }
+
+        #[test]
+        fn agrees_with_reference(size in 1..MAX_SIZE, seed in any::<u64>()) {
+            differential(size, seed);
+        }
+    }
+}
\ No newline at end of file
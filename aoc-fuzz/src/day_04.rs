@@ -0,0 +1,75 @@
+use day_04::card::scan_matches;
+use day_04::{generate, part1, part2};
+
+use crate::text;
+
+pub fn parse(data: &[u8]) {
+    let input = text(data);
+    let _ = part1::process(&input);
+    let _ = part2::process(&input);
+}
+
+// Matches through the old `Vec` scan, copies handed out card by card
+fn reference(input: &str) -> (u128, u128) {
+    let matches: Vec<usize> = input
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(':').unwrap();
+            let (winning, mine) = numbers.split_once('|').unwrap();
+            let list = |numbers: &str| -> Vec<u32> {
+                numbers.split_whitespace().map(|n| n.parse().unwrap()).collect()
+            };
+            scan_matches(&list(winning), &list(mine))
+        })
+        .collect();
+    let score = matches
+        .iter()
+        .map(|&m| if m == 0 { 0 } else { 1u128 << (m - 1) })
+        .sum();
+    let mut copies = vec![1u128; matches.len()];
+    for i in 0..matches.len() {
+        for j in i + 1..=i + matches[i] {
+            if j < copies.len() {
+                copies[j] += copies[i];
+            }
+        }
+    }
+    (score, copies.iter().sum())
+}
+
+// Generated inputs stay below this size, bigger ones only make the reference slow
+pub const MAX_SIZE: usize = 50;
+
+pub fn differential(size: usize, seed: u64) {
+    let input = generate::generate(size, seed);
+    let (score, copies) = reference(&input);
+    assert_eq!(score, part1::process(&input).unwrap(), "{input}");
+    assert_eq!(copies, part2::process(&input).unwrap(), "{input}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            parse(&data);
+        }
+
+        #[test]
+        fn never_panics_near_valid(
+            size in 0usize..20,
+            seed in any::<u64>(),
+            edits in prop::collection::vec(any::<(u16, u8)>(), 1..8),
+        ) {
+            parse(&crate::mutate(&day_04::generate::generate(size, seed), &edits));
+        }
+
+        #[test]
+        fn agrees_with_reference(size in 1..MAX_SIZE, seed in any::<u64>()) {
+            differential(size, seed);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use day_05::{generate, part1, part2};
+
+use crate::text;
+
+// Part 2 tries every seed of the ranges, which arbitrary input can make
+// astronomically large, so only its parser is checked
+pub fn parse(data: &[u8]) {
+    let input = text(data);
+    let _ = part1::process(&input);
+    let _ = day_05::parsed::parse(&input);
+}
+
+// The location of every seed, each map applied by scanning its rows
+fn locations(input: &str, seeds: &[u64]) -> Vec<u64> {
+    let maps: Vec<Vec<(u64, u64, u64)>> = input
+        .split("\n\n")
+        .skip(1)
+        .map(|section| {
+            section
+                .lines()
+                .skip(1)
+                .map(|row| {
+                    let row: Vec<u64> = row.split(' ').map(|n| n.parse().unwrap()).collect();
+                    (row[0], row[1], row[2])
+                })
+                .collect()
+        })
+        .collect();
+    seeds
+        .iter()
+        .map(|&seed| {
+            maps.iter().fold(seed, |current, map| {
+                map.iter()
+                    .find(|&&(_, source, length)| current >= source && current - source < length)
+                    .map_or(current, |&(destination, source, _)| destination + current - source)
+            })
+        })
+        .collect()
+}
+
+// Generated inputs stay below this size, bigger ones only make the reference slow
+pub const MAX_SIZE: usize = 8;
+
+pub fn differential(size: usize, seed: u64) {
+    let input = generate::generate(size, seed);
+    let (seeds_line, _) = input.split_once('\n').unwrap();
+    let seeds: Vec<u64> = seeds_line
+        .trim_start_matches("seeds: ")
+        .split(' ')
+        .map(|n| n.parse().unwrap())
+        .collect();
+    let lowest = locations(&input, &seeds).into_iter().min().unwrap();
+    assert_eq!(lowest, part1::process(&input).unwrap(), "{input}");
+
+    // part 2 is part 1 with every seed of the ranges spelled out
+    let expanded: Vec<u64> = seeds
+        .chunks(2)
+        .flat_map(|pair| pair[0]..pair[0] + pair[1])
+        .collect();
+    let lowest = locations(&input, &expanded).into_iter().min().unwrap();
+    assert_eq!(lowest, part2::process(&input).unwrap(), "{input}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            parse(&data);
+        }
+
+        #[test]
+        fn never_panics_near_valid(
+            size in 0usize..20,
+            seed in any::<u64>(),
+            edits in prop::collection::vec(any::<(u16, u8)>(), 1..8),
+        ) {
+            parse(&crate::mutate(&day_05::generate::generate(size, seed), &edits));
+        }
+
+        #[test]
+        fn agrees_with_reference(size in 1..MAX_SIZE, seed in any::<u64>()) {
+            differential(size, seed);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use day_06::race::Race;
+use day_06::{generate, part1, part2};
+
+use crate::text;
+
+pub fn parse(data: &[u8]) {
+    let input = text(data);
+    let _ = part1::process(&input);
+    let _ = part2::process(&input);
+}
+
+fn numbers(line: &str) -> Vec<&str> {
+    line.split_whitespace().skip(1).collect()
+}
+
+// Generated inputs stay below this size, bigger ones only make the reference slow
+pub const MAX_SIZE: usize = 10;
+
+pub fn differential(size: usize, seed: u64) {
+    let input = generate::generate(size, seed);
+    let lines: Vec<&str> = input.lines().collect();
+    let (times, distances) = (numbers(lines[0]), numbers(lines[1]));
+
+    let product: u128 = times
+        .iter()
+        .zip(&distances)
+        .map(|(time, distance)| {
+            let race = Race { time: time.parse().unwrap(), distance: distance.parse().unwrap() };
+            race.ways_to_win_brute_force()
+        })
+        .product();
+    assert_eq!(product, part1::process(&input).unwrap(), "{input}");
+
+    // Trying every hold time of the kerned race is only feasible for a few races
+    if size <= 3 {
+        let race = Race {
+            time: times.concat().parse().unwrap(),
+            distance: distances.concat().parse().unwrap(),
+        };
+        assert_eq!(race.ways_to_win_brute_force(), part2::process(&input).unwrap(), "{input}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            parse(&data);
+        }
+
+        #[test]
+        fn never_panics_near_valid(
+            size in 0usize..20,
+            seed in any::<u64>(),
+            edits in prop::collection::vec(any::<(u16, u8)>(), 1..8),
+        ) {
+            parse(&crate::mutate(&day_06::generate::generate(size, seed), &edits));
+        }
+
+        #[test]
+        fn agrees_with_reference(size in 1..MAX_SIZE, seed in any::<u64>()) {
+            differential(size, seed);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+// Checks shared by the libFuzzer targets in fuzz/ and the proptest runs of
+// `cargo test`. For every day:
+// - `parse` feeds arbitrary bytes to the parsers and solvers, which may
+//   return errors but must never panic
+// - `differential` solves a generated input and compares the answers with a
+//   straightforward reference implementation
+use std::borrow::Cow;
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+
+// Invalid UTF-8 is replaced rather than rejected, so every input reaches the
+// parsers
+pub fn text(data: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(data)
+}
+
+// Overwrites or inserts bytes of a valid input, the near misses find far more
+// parser bugs than random bytes do. Every edit is (position, byte), positions
+// wrap around the input and the low bit of the byte picks insert or overwrite.
+pub fn mutate(input: &str, edits: &[(u16, u8)]) -> Vec<u8> {
+    let mut bytes = input.as_bytes().to_vec();
+    for &(position, byte) in edits {
+        let position = position as usize % (bytes.len() + 1);
+        // mostly digits and separators, those are what the parsers care about
+        let replacement = b"0123456789 :;,|\n-x"[byte as usize % 18];
+        if byte & 1 == 0 || position == bytes.len() {
+            bytes.insert(position, replacement);
+        } else {
+            bytes[position] = replacement;
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate() {
+        assert_eq!(b"a0bc".to_vec(), mutate("abc", &[(1, 0)]));
+        assert_eq!(b"a1c".to_vec(), mutate("abc", &[(1, 1)]));
+        assert_eq!(b"abc1".to_vec(), mutate("abc", &[(3, 1)]));
+        assert_eq!(b"1".to_vec(), mutate("", &[(7, 1)]));
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use num_bigint::BigInt;
+use serde::{Serialize, Serializer};
+
+// What a part solves to. Numbers keep being numbers so answers can be
+// compared and added up, `Text` is for the puzzles whose answer isn't one.
+//
+// Numbers are equal by value whichever variant holds them, so `Unsigned(5)`,
+// `Big(5)` and `Text("5")` are all the same answer.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Big(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    // The answer as a number, `Text` only if it is one
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(text) => text.parse().ok(),
+        }
+    }
+
+    // The sum of two numeric answers, `None` if either is text
+    pub fn checked_add(&self, other: &Answer) -> Option<Answer> {
+        Some(Answer::from(self.to_bigint()? + other.to_bigint()?))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) if a == b => true,
+            _ => self.to_bigint().is_some_and(|a| other.to_bigint() == Some(a)),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+// Consistent with `eq`, numbers hash by value
+impl Hash for Answer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match (self.to_bigint(), self) {
+            (Some(n), _) => n.hash(state),
+            (None, Answer::Text(text)) => text.hash(state),
+            (None, _) => unreachable!("only text can fail to be a number"),
+        }
+    }
+}
+
+// Numbers that fit in 64 bits stay JSON numbers, bigger ones become strings
+// so nothing reading the JSON rounds them
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Unsigned(n) => serializer.serialize_u64(*n),
+            Answer::Signed(n) => serializer.serialize_i64(*n),
+            Answer::Big(n) => serializer.collect_str(n),
+            Answer::Text(text) => serializer.serialize_str(text),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident, $as:ty, $($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer::$variant(n as $as)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(Unsigned, u64, u8, u16, u32, u64, usize);
+from_integer!(Signed, i64, i8, i16, i32, i64, isize);
+
+macro_rules! eq_integer {
+    ($($int:ty),*) => {
+        $(
+            impl PartialEq<$int> for Answer {
+                fn eq(&self, other: &$int) -> bool {
+                    self.to_bigint() == Some(BigInt::from(*other))
+                }
+            }
+
+            impl PartialEq<Answer> for $int {
+                fn eq(&self, other: &Answer) -> bool {
+                    other == self
+                }
+            }
+        )*
+    };
+}
+
+eq_integer!(u8, u16, u32, u64, usize, u128, i8, i16, i32, i64, isize, i128);
+
+impl From<BigInt> for Answer {
+    // Small enough numbers go back to `Unsigned` or `Signed`
+    fn from(n: BigInt) -> Self {
+        if let Ok(n) = u64::try_from(&n) {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = i64::try_from(&n) {
+            Answer::Signed(n)
+        } else {
+            Answer::Big(n)
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        Answer::from(BigInt::from(n))
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Answer::from(BigInt::from(n))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Answer::Text(text) if text == other => true,
+            _ => other.parse::<BigInt>().is_ok_and(|n| self.to_bigint() == Some(n)),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<String> for Answer {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Answer> for String {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Answer::from(142u32), "142")]
+    #[case(Answer::from(-3i64), "-3")]
+    #[case(Answer::from(u128::MAX), "340282366920938463463374607431768211455")]
+    #[case(Answer::from("LDHX"), "LDHX")]
+    fn test_display(#[case] answer: Answer, #[case] expected: &str) {
+        assert_eq!(expected, answer.to_string());
+        assert_eq!(answer, expected);
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(Answer::from(35u64), 35);
+        assert_eq!(46, Answer::from(46usize));
+        assert_ne!(Answer::from(46u32), 47);
+        assert_eq!(Answer::from(-1i32), -1);
+        assert_eq!(Answer::from("8"), 8);
+    }
+
+    #[test]
+    fn test_equal_across_variants() {
+        assert_eq!(Answer::Unsigned(5), Answer::Signed(5));
+        assert_eq!(Answer::Unsigned(5), Answer::Big(BigInt::from(5)));
+        assert_eq!(Answer::Text("5".to_string()), Answer::Signed(5));
+        assert_ne!(Answer::Text("five".to_string()), Answer::Signed(5));
+        assert_ne!(Answer::Unsigned(5), Answer::Signed(-5));
+    }
+
+    #[test]
+    fn test_hash_matches_eq() {
+        let answers: std::collections::HashSet<Answer> =
+            [Answer::Unsigned(5), Answer::Signed(5), Answer::from("5"), Answer::from("five")].into_iter().collect();
+        assert_eq!(2, answers.len());
+    }
+
+    #[test]
+    fn test_small_big_integers_shrink() {
+        assert!(matches!(Answer::from(12u128), Answer::Unsigned(12)));
+        assert!(matches!(Answer::from(-12i128), Answer::Signed(-12)));
+        assert!(matches!(Answer::from(i128::MIN), Answer::Big(_)));
+    }
+
+    #[test]
+    fn test_checked_add() {
+        let total = Answer::from(u64::MAX).checked_add(&Answer::from(1u8)).unwrap();
+        assert_eq!("18446744073709551616", total.to_string());
+        assert_eq!(Some(Answer::from(2i32)), Answer::from(3u8).checked_add(&Answer::from(-1i8)));
+        assert_eq!(None, Answer::from("LDHX").checked_add(&Answer::from(1u8)));
+    }
+
+    #[test]
+    fn test_json() {
+        let json = |answer: Answer| serde_json::to_string(&answer).unwrap();
+        assert_eq!("142", json(Answer::from(142u32)));
+        assert_eq!("-3", json(Answer::from(-3i8)));
+        assert_eq!("\"340282366920938463463374607431768211455\"", json(Answer::from(u128::MAX)));
+        assert_eq!("\"LDHX\"", json(Answer::from("LDHX")));
+    }
+}
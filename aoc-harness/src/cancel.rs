@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// Lets the runner ask a part to stop once it's over its time limit. Parts
+// can't be killed from outside, so long-running solvers poll `is_cancelled()`
+// every so often and bail out with an error:
+//
+// if aoc_harness::cancel::is_cancelled() {
+//     return Err(AocError::Cancelled);
+// }
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+// Makes `token` the one `is_cancelled()` checks while `f` runs on this thread
+pub fn with_token<R>(token: &CancellationToken, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(token.clone())));
+    // restore the previous token even if `f` panics
+    struct Restore(Option<CancellationToken>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(previous);
+    f()
+}
+
+// The token of the part running on this thread, for solvers that hand work
+// to other threads
+pub fn current() -> Option<CancellationToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+// Whether the runner gave up on the part running on this thread. Always
+// false outside the runner, e.g. in tests and benches.
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().as_ref().is_some_and(CancellationToken::is_cancelled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_token() {
+        assert!(!is_cancelled());
+        assert!(current().is_none());
+        let token = CancellationToken::new();
+        with_token(&token, || {
+            assert!(!is_cancelled());
+            assert!(current().is_some());
+            token.cancel();
+            assert!(is_cancelled());
+        });
+        // only the thread running under the token sees it
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn test_token_is_restored_after_a_panic() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = std::panic::catch_unwind(|| with_token(&token, || panic!("solver bug")));
+        assert!(result.is_err());
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_from_another_thread() {
+        let token = CancellationToken::new();
+        let worker = {
+            let token = token.clone();
+            std::thread::spawn(move || {
+                with_token(&token, || {
+                    while !is_cancelled() {
+                        std::thread::yield_now();
+                    }
+                })
+            })
+        };
+        token.cancel();
+        worker.join().unwrap();
+    }
+}
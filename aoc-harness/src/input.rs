@@ -0,0 +1,268 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+const BOM: char = '\u{feff}';
+
+// The parsers expect LF line endings, no trailing whitespace and exactly one
+// newline at the end. Every day's `parse` runs its input through `normalise`
+// first, so inputs saved on Windows or pasted from a browser still parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    // Fix what's off and carry on
+    #[default]
+    Fix,
+    // Report what's off instead, e.g. to find out why an input is different.
+    // Days can read `mode` to be strict about their own puzzle rules too.
+    Strict,
+}
+
+thread_local! {
+    static MODE: Cell<Mode> = const { Cell::new(Mode::Fix) };
+}
+
+// Makes `normalise` use `mode` while `f` runs on this thread
+pub fn with_mode<R>(mode: Mode, f: impl FnOnce() -> R) -> R {
+    let previous = MODE.with(|current| current.replace(mode));
+    // restore the previous mode even if `f` panics
+    struct Restore(Mode);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            MODE.with(|current| current.set(self.0));
+        }
+    }
+    let _restore = Restore(previous);
+    f()
+}
+
+// The mode on this thread, `Fix` unless the runner says otherwise
+pub fn mode() -> Mode {
+    MODE.with(Cell::get)
+}
+
+// Lines are counted from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    ByteOrderMark,
+    CarriageReturns { first_line: usize, lines: usize },
+    TrailingWhitespace { first_line: usize, lines: usize },
+    TrailingBlankLines,
+    MissingFinalNewline,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::ByteOrderMark => write!(f, "it starts with a byte order mark"),
+            Issue::CarriageReturns { first_line, lines: 1 } => write!(f, "line {first_line} ends in CRLF"),
+            Issue::CarriageReturns { first_line, lines } => {
+                write!(f, "{lines} lines end in CRLF, the first is line {first_line}")
+            }
+            Issue::TrailingWhitespace { first_line, lines: 1 } => {
+                write!(f, "line {first_line} has trailing whitespace")
+            }
+            Issue::TrailingWhitespace { first_line, lines } => {
+                write!(f, "{lines} lines have trailing whitespace, the first is line {first_line}")
+            }
+            Issue::TrailingBlankLines => write!(f, "it ends in blank lines"),
+            Issue::MissingFinalNewline => write!(f, "the last line has no newline"),
+        }
+    }
+}
+
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+#[error("the input isn't normalised: {}", .issues.iter().map(Issue::to_string).collect::<Vec<_>>().join(", "))]
+#[diagnostic(
+    code(aoc::input_not_normalised),
+    help("save it with LF line endings, without a BOM and with a final newline, or leave out --strict")
+)]
+pub struct NotNormalised {
+    pub issues: Vec<Issue>,
+}
+
+// Splits the input after its BOMs into the lines up to the last one with
+// anything on it, without that line's newline, and what comes after the
+// newline. `None` for blank inputs, the rest is `None` without a newline.
+fn split(body: &str) -> Option<(&str, Option<&str>)> {
+    let end = body.trim_end().len();
+    if end == 0 {
+        return None;
+    }
+    Some(match body[end..].find('\n') {
+        Some(i) => (&body[..end + i], Some(&body[end + i + 1..])),
+        None => (body, None),
+    })
+}
+
+// Everything `normalise` would fix about the input
+pub fn issues(input: &str) -> Vec<Issue> {
+    let mut issues = vec![];
+    let body = input.trim_start_matches(BOM);
+    if body.len() != input.len() {
+        issues.push(Issue::ByteOrderMark);
+    }
+    let Some((content, rest)) = split(body) else {
+        if !body.is_empty() {
+            issues.push(Issue::TrailingBlankLines);
+        }
+        return issues;
+    };
+
+    let mut carriage_returns = None;
+    let mut trailing_whitespace = None;
+    let count = |found: &mut Option<(usize, usize)>, line: usize| {
+        let (_, lines) = found.get_or_insert((line, 0));
+        *lines += 1;
+    };
+    let mut lines = content.split('\n').enumerate().peekable();
+    while let Some((i, line)) = lines.next() {
+        // a CR without a newline after it is just whitespace
+        let terminated = lines.peek().is_some() || rest.is_some();
+        let line = match line.strip_suffix('\r') {
+            Some(line) if terminated => {
+                count(&mut carriage_returns, i + 1);
+                line
+            }
+            _ => line,
+        };
+        if line.len() != line.trim_end().len() {
+            count(&mut trailing_whitespace, i + 1);
+        }
+    }
+    if let Some((first_line, lines)) = carriage_returns {
+        issues.push(Issue::CarriageReturns { first_line, lines });
+    }
+    if let Some((first_line, lines)) = trailing_whitespace {
+        issues.push(Issue::TrailingWhitespace { first_line, lines });
+    }
+    match rest {
+        None => issues.push(Issue::MissingFinalNewline),
+        Some(rest) if !rest.is_empty() => issues.push(Issue::TrailingBlankLines),
+        Some(_) => {}
+    }
+    issues
+}
+
+// The input the way the parsers expect it. Inputs that already are come
+// back borrowed, the rest are fixed, or reported in `Mode::Strict`.
+pub fn normalise(input: &str) -> Result<Cow<'_, str>, NotNormalised> {
+    let issues = issues(input);
+    if issues.is_empty() {
+        return Ok(Cow::Borrowed(input));
+    }
+    if mode() == Mode::Strict {
+        return Err(NotNormalised { issues });
+    }
+    let mut fixed = String::with_capacity(input.len() + 1);
+    if let Some((content, _)) = split(input.trim_start_matches(BOM)) {
+        for line in content.split('\n') {
+            fixed.push_str(line.trim_end());
+            fixed.push('\n');
+        }
+    }
+    Ok(Cow::Owned(fixed))
+}
+
+// Generates tests that the examples matching the glob still solve to their
+// answers when saved with CRLF line endings, a BOM or trailing whitespace,
+// and that strict mode reports those instead. Call it next to
+// `example_tests!`:
+//
+// aoc_harness::normalisation_tests!(process, "examples/part1/*.txt");
+#[macro_export]
+macro_rules! normalisation_tests {
+    ($process:path, $glob:tt $(, ignore = $reason:tt)?) => {
+        mod normalisation {
+            use super::*;
+            // rstest's generated cases refer to `PathBuf` unqualified
+            use std::path::PathBuf;
+
+            #[rstest::rstest]
+            $(#[ignore = $reason])?
+            fn file(#[files($glob)] path: PathBuf) -> miette::Result<()> {
+                let example = $crate::Example::load(&path)?;
+                let crlf = example.input.replace('\n', "\r\n");
+                let bom = format!("\u{feff}{}", example.input);
+                let messy = example.input.trim_end().replace('\n', " \t\n");
+                for (name, input) in [("CRLF", &crlf), ("BOM", &bom), ("trailing whitespace", &messy)] {
+                    assert_eq!(example.expected, $process(input)?.to_string(), "{} with {name}", path.display());
+                    let strict = $crate::input::with_mode($crate::input::Mode::Strict, || $process(input));
+                    assert!(strict.is_err(), "{} with {name} passed strict mode", path.display());
+                }
+                // most examples are copied without a final newline
+                let clean = $crate::input::normalise(&example.input)?;
+                let strict = $crate::input::with_mode($crate::input::Mode::Strict, || $process(&clean));
+                assert_eq!(example.expected, strict?.to_string(), "{} in strict mode", path.display());
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", "")]
+    #[case("a\nb\n", "a\nb\n")]
+    #[case("a\r\nb\r\n", "a\nb\n")]
+    #[case("\u{feff}a\nb\n", "a\nb\n")]
+    #[case("a  \nb\t\n", "a\nb\n")]
+    #[case("a\nb", "a\nb\n")]
+    #[case("a\nb\n\n\n", "a\nb\n")]
+    #[case("a\n\nb\n", "a\n\nb\n")]
+    #[case(" \r\n\n", "")]
+    fn test_normalise(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(expected, normalise(input).unwrap());
+    }
+
+    #[test]
+    fn test_clean_input_is_borrowed() {
+        assert!(matches!(normalise("seeds: 79 14\n\nmap:\n1 2 3\n"), Ok(Cow::Borrowed(_))));
+    }
+
+    #[rstest]
+    #[case("a\nb\n", vec![])]
+    #[case("\u{feff}a\n", vec![Issue::ByteOrderMark])]
+    #[case("a\nb\r\nc\r\n", vec![Issue::CarriageReturns { first_line: 2, lines: 2 }])]
+    #[case("a \nb\nc\t\n", vec![Issue::TrailingWhitespace { first_line: 1, lines: 2 }])]
+    #[case("a\nb", vec![Issue::MissingFinalNewline])]
+    #[case("a\nb  ", vec![Issue::TrailingWhitespace { first_line: 2, lines: 1 }, Issue::MissingFinalNewline])]
+    #[case("a \r\nb \n", vec![Issue::CarriageReturns { first_line: 1, lines: 1 }, Issue::TrailingWhitespace { first_line: 1, lines: 2 }])]
+    #[case("a\n \n", vec![Issue::TrailingBlankLines])]
+    #[case("a\r\nb", vec![Issue::CarriageReturns { first_line: 1, lines: 1 }, Issue::MissingFinalNewline])]
+    #[case("\u{feff}\u{feff}", vec![Issue::ByteOrderMark])]
+    #[case("a\n\n", vec![Issue::TrailingBlankLines])]
+    #[case("a\r\n\r\n", vec![Issue::CarriageReturns { first_line: 1, lines: 1 }, Issue::TrailingBlankLines])]
+    fn test_issues(#[case] input: &str, #[case] expected: Vec<Issue>) {
+        assert_eq!(expected, issues(input));
+    }
+
+    #[test]
+    fn test_strict_mode_reports() {
+        let error = with_mode(Mode::Strict, || normalise("\u{feff}a\r\n")).unwrap_err();
+        assert_eq!(
+            "the input isn't normalised: it starts with a byte order mark, line 1 ends in CRLF",
+            error.to_string()
+        );
+        assert!(with_mode(Mode::Strict, || normalise("a\n")).is_ok());
+        // only while `f` runs
+        assert_eq!(Mode::Fix, mode());
+        assert!(normalise("\u{feff}a\r\n").is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn test_normalised_has_no_issues(input in "[\u{feff}ab \t\r\n]{0,30}") {
+            let normalised = normalise(&input).unwrap();
+            prop_assert_eq!(Vec::<Issue>::new(), issues(&normalised));
+            prop_assert_eq!(&normalised, &normalise(&normalised).unwrap());
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+pub mod answer;
+pub mod cancel;
+pub mod input;
+
+pub use answer::Answer;
+
+// Example files start with the expected answer, followed by a separator line
+// and then the puzzle input exactly as it appears in the puzzle:
+//
+// expected: 4361
+// ---
+// 467..114..
+// ...*......
+pub const EXPECTED_PREFIX: &str = "expected:";
+pub const SEPARATOR: &str = "---";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub expected: String,
+    pub input: String,
+}
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum ExampleError {
+    #[error("could not read example {path}")]
+    #[diagnostic(code(aoc::example_io))]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("example {path} does not start with an `{EXPECTED_PREFIX} <answer>` line")]
+    #[diagnostic(code(aoc::example_missing_expected))]
+    MissingExpected { path: String },
+
+    #[error("example {path} has no `{SEPARATOR}` line between the answer and the input")]
+    #[diagnostic(code(aoc::example_missing_separator))]
+    MissingSeparator { path: String },
+}
+
+impl Example {
+    pub fn parse(path: &str, contents: &str) -> Result<Self, ExampleError> {
+        let (header, rest) = contents.split_once('\n').unwrap_or((contents, ""));
+        let expected = header
+            .trim_end_matches('\r')
+            .strip_prefix(EXPECTED_PREFIX)
+            .ok_or_else(|| ExampleError::MissingExpected { path: path.to_string() })?
+            .trim();
+        let (separator, input) = rest.split_once('\n').unwrap_or((rest, ""));
+        if separator.trim_end_matches('\r') != SEPARATOR {
+            return Err(ExampleError::MissingSeparator { path: path.to_string() });
+        }
+        Ok(Self { expected: expected.to_string(), input: input.to_string() })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ExampleError> {
+        let display = path.display().to_string();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|source| ExampleError::Io { path: display.clone(), source })?;
+        Self::parse(&display, &contents)
+    }
+}
+
+// One way of solving a part from the day's parsed input. Days list theirs in
+// `partN::VARIANTS`, the first one is the default the runner and the
+// binaries use.
+pub struct Variant<P: 'static> {
+    pub name: &'static str,
+    pub solve: fn(&P) -> miette::Result<Answer>,
+}
+
+// derived impls would want `P: Clone`
+impl<P> Clone for Variant<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for Variant<P> {}
+
+impl<P> std::fmt::Display for Variant<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<P> std::fmt::Debug for Variant<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Variant").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+// A day's parser and the variants of both its parts. Both parts solve the
+// same `Parsed` type, so an input only has to be parsed once. Days export
+// theirs as `PUZZLE` from lib.rs.
+pub struct Puzzle<P: 'static> {
+    pub parse: fn(&str) -> miette::Result<P>,
+    pub parts: [&'static [Variant<P>]; 2],
+}
+
+// Runs every variant on the parsed input and returns their common answer
+pub fn assert_variants_agree<P>(variants: &[Variant<P>], parsed: &P) -> miette::Result<Answer> {
+    let mut answers = vec![];
+    for variant in variants {
+        answers.push((variant.name, (variant.solve)(parsed)?));
+    }
+    let (_, first) = &answers[0];
+    assert!(answers.iter().all(|(_, answer)| answer == first), "the variants disagree: {answers:?}");
+    Ok(first.clone())
+}
+
+// Generates an rstest case for every example file matching the glob, relative
+// to the day's crate. Call it from a part's test module:
+//
+// aoc_harness::example_tests!(process, "examples/part1/*.txt");
+//
+// Add `ignore = "reason"` to skip the examples, e.g. while they're still empty.
+// Days should have a build.rs with `cargo:rerun-if-changed=examples` so new
+// files are picked up without touching any Rust.
+#[macro_export]
+macro_rules! example_tests {
+    ($process:path, $glob:tt $(, ignore = $reason:tt)?) => {
+        mod examples {
+            use super::*;
+            // rstest's generated cases refer to `PathBuf` unqualified
+            use std::path::PathBuf;
+
+            #[rstest::rstest]
+            $(#[ignore = $reason])?
+            fn file(#[files($glob)] path: PathBuf) -> miette::Result<()> {
+                let example = $crate::Example::load(&path)?;
+                assert_eq!(
+                    example.expected,
+                    $process(&example.input)?.to_string(),
+                    "{}",
+                    path.display()
+                );
+                Ok(())
+            }
+        }
+    };
+}
+
+// How much running a part on its example may allocate: the number of
+// allocations over the whole run and the most bytes live at any one time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub blocks: u64,
+    pub peak_bytes: usize,
+}
+
+// dhat only allows one profiler at a time
+static PROFILER: Mutex<()> = Mutex::new(());
+
+// Runs `process` on an example under dhat's testing mode and fails when it
+// goes over the budget, leaving the heap profile in dhat-heap.json. dhat
+// counts every allocation in the process, so call it from a test binary of
+// its own that installs `dhat::Alloc` as the global allocator, see
+// day-*/tests/allocations.rs.
+pub fn assert_allocations<T, E>(
+    path: &Path,
+    process: impl Fn(&str) -> Result<T, E>,
+    budget: Budget,
+) -> miette::Result<()>
+where
+    T: ToString,
+    E: Diagnostic + Send + Sync + 'static,
+{
+    let example = Example::load(path)?;
+    let _lock = PROFILER.lock().unwrap_or_else(PoisonError::into_inner);
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let answer = process(&example.input)?;
+    let stats = dhat::HeapStats::get();
+    dhat::assert!(
+        stats.total_blocks <= budget.blocks,
+        "{}: {} allocations, the budget is {}",
+        path.display(),
+        stats.total_blocks,
+        budget.blocks
+    );
+    dhat::assert!(
+        stats.max_bytes <= budget.peak_bytes,
+        "{}: peaked at {} bytes, the budget is {}",
+        path.display(),
+        stats.max_bytes,
+        budget.peak_bytes
+    );
+    assert_eq!(example.expected, answer.to_string(), "{}", path.display());
+    Ok(())
+}
+
+// Like `example_tests!`, but checks every variant of the part against the
+// example files, parsing each one once:
+//
+// aoc_harness::variant_tests!(crate::parsed::parse, VARIANTS, "examples/part2/*.txt");
+#[macro_export]
+macro_rules! variant_tests {
+    ($parse:path, $variants:path, $glob:tt $(, ignore = $reason:tt)?) => {
+        mod variants {
+            use super::*;
+            use std::path::PathBuf;
+
+            #[rstest::rstest]
+            $(#[ignore = $reason])?
+            fn file(#[files($glob)] path: PathBuf) -> miette::Result<()> {
+                let example = $crate::Example::load(&path)?;
+                let parsed = $parse(&example.input)?;
+                for variant in $variants {
+                    assert_eq!(
+                        example.expected,
+                        (variant.solve)(&parsed)?,
+                        "{} with the {} variant",
+                        path.display(),
+                        variant.name
+                    );
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let example = Example::parse("example.txt", "expected: 142\n---\n1abc2\ntreb7uchet\n").unwrap();
+        assert_eq!("142", example.expected);
+        assert_eq!("1abc2\ntreb7uchet\n", example.input);
+    }
+
+    #[test]
+    fn test_parse_keeps_input_verbatim() {
+        // blank lines and a missing final newline are part of the input
+        let example = Example::parse("example.txt", "expected: 35\r\n---\r\nseeds: 79\n\nmap:").unwrap();
+        assert_eq!("35", example.expected);
+        assert_eq!("seeds: 79\n\nmap:", example.input);
+    }
+
+    #[test]
+    fn test_assert_variants_agree() -> miette::Result<()> {
+        let variants: [Variant<&str>; 2] = [
+            Variant { name: "len", solve: |input| Ok(input.len().into()) },
+            Variant { name: "chars", solve: |input| Ok(input.chars().count().into()) },
+        ];
+        assert_eq!(3, assert_variants_agree(&variants, &"abc")?);
+        let disagree = std::panic::catch_unwind(|| assert_variants_agree(&variants, &"é"));
+        assert!(disagree.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Example::parse("a.txt", "467..114..\n...*......"),
+            Err(ExampleError::MissingExpected { .. })
+        ));
+        assert!(matches!(
+            Example::parse("a.txt", "expected: 4361\n467..114.."),
+            Err(ExampleError::MissingSeparator { .. })
+        ));
+    }
+}
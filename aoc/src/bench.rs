@@ -0,0 +1,319 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use crate::custom_error::AocError;
+use crate::history::{Harness, Measurement, Run};
+
+// A `[[bench]]` target of one of the day crates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub day: String,
+    pub name: String,
+    pub harness: Harness,
+}
+
+pub struct Workspace {
+    pub root: PathBuf,
+    pub target_dir: PathBuf,
+    pub targets: Vec<Target>,
+}
+
+fn metadata_str<'a>(value: &'a Value, key: &str) -> Result<&'a str, AocError> {
+    value[key]
+        .as_str()
+        .ok_or_else(|| AocError::Metadata(format!("missing `{key}`")))
+}
+
+// Reads the bench targets of every `day-*` package from `cargo metadata`
+#[tracing::instrument]
+pub fn workspace() -> Result<Workspace, AocError> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(AocError::CommandFailed {
+            command: "cargo metadata".to_string(),
+            status: output.status,
+        });
+    }
+    let metadata: Value = serde_json::from_slice(&output.stdout)?;
+
+    let mut targets = vec![];
+    let packages = metadata["packages"]
+        .as_array()
+        .ok_or_else(|| AocError::Metadata("missing `packages`".to_string()))?;
+    for package in packages {
+        let day = metadata_str(package, "name")?;
+        if !day.starts_with("day-") {
+            continue;
+        }
+        for target in package["targets"].as_array().into_iter().flatten() {
+            let is_bench = target["kind"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().any(|kind| kind == "bench"));
+            if is_bench {
+                let name = metadata_str(target, "name")?;
+                let harness = if name.ends_with("-criterion") {
+                    Harness::Criterion
+                } else {
+                    Harness::Divan
+                };
+                targets.push(Target { day: day.to_string(), name: name.to_string(), harness });
+            }
+        }
+    }
+    targets.sort_by(|a, b| (&a.day, &a.name).cmp(&(&b.day, &b.name)));
+
+    Ok(Workspace {
+        root: metadata_str(&metadata, "workspace_root")?.into(),
+        target_dir: metadata_str(&metadata, "target_directory")?.into(),
+        targets,
+    })
+}
+
+// e.g. "303.6 µs"
+pub fn parse_duration(text: &str) -> Result<f64, AocError> {
+    let bad = || AocError::BadDuration(text.to_string());
+    let (value, unit) = text.trim().split_once(' ').ok_or_else(bad)?;
+    let value: f64 = value.parse().map_err(|_| bad())?;
+    let scale = match unit.trim() {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return Err(bad()),
+    };
+    Ok(value * scale)
+}
+
+// Pulls the medians out of a divan table:
+//
+// day_04_bench  fastest       │ slowest       │ median        │ mean   ...
+// ├─ part1      272.1 µs      │ 518.7 µs      │ 303.6 µs      │ 321.7 µs ...
+// ╰─ scaled                   │               │               │        ...
+//    ├─ 10      1.2 ms        │ ...
+//
+// Nested benchmarks (modules, args, consts) are named by their path, e.g.
+// `scaled/10`.
+pub fn parse_divan(output: &str) -> Result<Vec<(String, f64)>, AocError> {
+    let mut results = vec![];
+    let mut path: Vec<String> = vec![];
+    for line in output.lines() {
+        let Some(start) = line.find(|c: char| !matches!(c, ' ' | '│' | '├' | '╰' | '─')) else {
+            continue;
+        };
+        let prefix = &line[..start];
+        if !prefix.contains('├') && !prefix.contains('╰') {
+            continue;
+        }
+        // every level of the tree is indented by three characters
+        let depth = prefix.chars().count() / 3 - 1;
+        let mut columns = line[start..].split('│');
+        let name = columns
+            .next()
+            .and_then(|first| first.split_whitespace().next())
+            .unwrap_or_default();
+        path.truncate(depth);
+        path.push(name.to_string());
+
+        let median = columns.nth(1).map(str::trim).unwrap_or_default();
+        if !median.is_empty() {
+            results.push((path.join("/"), parse_duration(median)?));
+        }
+    }
+    Ok(results)
+}
+
+// Criterion keeps its results in target/criterion/<group>/<function>/new,
+// only the ones written after `since` belong to this run
+pub fn read_criterion(dir: &Path, since: SystemTime) -> Result<Vec<(String, f64)>, AocError> {
+    let mut results = vec![];
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let fresh = path.metadata()?.modified()? >= since;
+            if !fresh || path.file_name() != Some("estimates.json".as_ref()) {
+                continue;
+            }
+            if path.parent().and_then(Path::file_name) != Some("new".as_ref()) {
+                continue;
+            }
+            let benchmark: Value =
+                serde_json::from_str(&std::fs::read_to_string(path.with_file_name("benchmark.json"))?)?;
+            let estimates: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            let median = estimates["median"]["point_estimate"]
+                .as_f64()
+                .ok_or_else(|| AocError::BadDuration(path.display().to_string()))?;
+            results.push((criterion_name(&benchmark), median));
+        }
+    }
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(results)
+}
+
+// "day_05::part1" / "part1" is reported as "part1", anything else keeps its
+// whole id
+fn criterion_name(benchmark: &Value) -> String {
+    let id = benchmark["full_id"].as_str().unwrap_or_default();
+    match id.split_once("::") {
+        Some((_, rest)) => match rest.split_once('/') {
+            Some((group, function)) if group == function => group.to_string(),
+            _ => rest.to_string(),
+        },
+        None => id.to_string(),
+    }
+}
+
+// Runs a single bench target, passing `filter` on to the harness
+#[tracing::instrument(skip(workspace))]
+pub fn run_target(workspace: &Workspace, target: &Target, filter: Option<&str>) -> Result<Vec<Measurement>, AocError> {
+    let started = SystemTime::now();
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(&workspace.root)
+        .args(["bench", "-q", "-p", &target.day, "--bench", &target.name, "--"])
+        .stderr(Stdio::inherit());
+    if target.harness == Harness::Criterion {
+        command.arg("--noplot");
+    }
+    command.args(filter);
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(AocError::CommandFailed {
+            command: format!("cargo bench -p {} --bench {}", target.day, target.name),
+            status: output.status,
+        });
+    }
+
+    let results = match target.harness {
+        Harness::Divan => parse_divan(&String::from_utf8_lossy(&output.stdout))?,
+        Harness::Criterion => read_criterion(&workspace.target_dir.join("criterion"), started)?,
+    };
+    Ok(results
+        .into_iter()
+        .map(|(bench, median_ns)| Measurement {
+            day: target.day.clone(),
+            target: target.name.clone(),
+            bench,
+            harness: target.harness,
+            median_ns,
+        })
+        .collect())
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String, AocError> {
+    let output = Command::new("git").current_dir(root).args(args).output()?;
+    if !output.status.success() {
+        return Err(AocError::CommandFailed {
+            command: format!("git {}", args.join(" ")),
+            status: output.status,
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Runs the benchmarks of the given days (all of them when empty)
+pub fn run(workspace: &Workspace, days: &[String], harness: Option<Harness>, filter: Option<&str>) -> Result<Run, AocError> {
+    let targets: Vec<&Target> = workspace
+        .targets
+        .iter()
+        .filter(|target| days.is_empty() || days.contains(&target.day))
+        .filter(|target| harness.is_none_or(|harness| harness == target.harness))
+        .collect();
+    if targets.is_empty() {
+        return Err(AocError::NoBenches(if days.is_empty() {
+            "the workspace".to_string()
+        } else {
+            days.join(", ")
+        }));
+    }
+
+    let mut results = vec![];
+    for target in targets {
+        eprintln!("benchmarking {} {}", target.day, target.name);
+        results.extend(run_target(workspace, target, filter)?);
+    }
+    Ok(Run {
+        commit: git(&workspace.root, &["rev-parse", "--short", "HEAD"])?,
+        dirty: !git(&workspace.root, &["status", "--porcelain"])?.is_empty(),
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs()),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("27.02 ns", 27.02)]
+    #[case("303.6 µs", 303_600.0)]
+    #[case("1.5 ms", 1_500_000.0)]
+    #[case("2 s", 2e9)]
+    #[case("500 ps", 0.5)]
+    fn test_parse_duration(#[case] text: &str, #[case] expected: f64) -> miette::Result<()> {
+        assert!((expected - parse_duration(text)?).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_duration_errors() {
+        assert!(parse_duration("12").is_err());
+        assert!(parse_duration("12 fortnights").is_err());
+    }
+
+    #[test]
+    fn test_parse_divan() -> miette::Result<()> {
+        let output = "Timer precision: 20 ns
+day_04_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1      272.1 µs      │ 518.7 µs      │ 303.6 µs      │ 321.7 µs      │ 100     │ 100
+╰─ part2      1.1 ms        │ 2 ms          │ 1.2 ms        │ 1.3 ms        │ 100     │ 100
+";
+        assert_eq!(
+            vec![("part1".to_string(), 303_600.0), ("part2".to_string(), 1_200_000.0)],
+            parse_divan(output)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_divan_nested() -> miette::Result<()> {
+        let output = "day_03_bench   fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1       10 µs         │ 10 µs         │ 10 µs         │ 10 µs         │ 100     │ 100
+╰─ scaled                    │               │               │               │         │
+   ├─ parse                  │               │               │               │         │
+   │  ├─ 1     1 µs          │ 1 µs          │ 1 µs          │ 1 µs          │ 100     │ 100
+   │  ╰─ 10    10 µs         │ 10 µs         │ 10 µs         │ 10 µs         │ 100     │ 100
+   ╰─ solve    2 µs          │ 2 µs          │ 2 µs          │ 2 µs          │ 100     │ 100
+";
+        let names: Vec<String> = parse_divan(output)?.into_iter().map(|(name, _)| name).collect();
+        assert_eq!(vec!["part1", "scaled/parse/1", "scaled/parse/10", "scaled/solve"], names);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(r#"{"full_id": "day_05::part1/part1"}"#, "part1")]
+    #[case(r#"{"full_id": "day_05::part1/scaled/10"}"#, "part1/scaled/10")]
+    #[case(r#"{"full_id": "parse"}"#, "parse")]
+    fn test_criterion_name(#[case] json: &str, #[case] expected: &str) -> miette::Result<()> {
+        let benchmark: Value = serde_json::from_str(json).map_err(AocError::from)?;
+        assert_eq!(expected, criterion_name(&benchmark));
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::custom_error::AocError;
+use crate::history::{format_change, format_duration, Measurement, Run};
+
+// Baselines are whole runs saved under benchmarks/baselines/<name>.json
+pub fn baseline_path(root: &Path, name: &str) -> PathBuf {
+    root.join("benchmarks").join("baselines").join(format!("{name}.json"))
+}
+
+pub fn save_baseline(path: &Path, run: &Run) -> Result<(), AocError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut json = serde_json::to_string_pretty(run)?;
+    json.push('\n');
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+pub fn load_baseline(path: &Path, name: &str) -> Result<Run, AocError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(AocError::MissingBaseline { name: name.to_string() })
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Unchanged,
+    Improved,
+    Regressed,
+    // Not in the baseline, nothing to compare against
+    New,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Unchanged => write!(f, "ok"),
+            Status::Improved => write!(f, "improved"),
+            Status::Regressed => write!(f, "REGRESSED"),
+            Status::New => write!(f, "new"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diff<'a> {
+    pub current: &'a Measurement,
+    pub baseline: Option<&'a Measurement>,
+    pub status: Status,
+}
+
+impl Diff<'_> {
+    pub fn change(&self) -> Option<f64> {
+        Some(self.current.median_ns / self.baseline?.median_ns - 1.0)
+    }
+}
+
+// Compares every benchmark of the new run with the baseline, a median more
+// than `threshold` (0.1 is 10%) slower is a regression. Baseline benchmarks
+// that weren't rerun, e.g. because only one day was benchmarked, are skipped.
+pub fn compare<'a>(baseline: &'a Run, current: &'a Run, threshold: f64) -> Vec<Diff<'a>> {
+    let baseline: HashMap<_, _> = baseline
+        .results
+        .iter()
+        .map(|measurement| (measurement.key(), measurement))
+        .collect();
+    current
+        .results
+        .iter()
+        .map(|measurement| {
+            let baseline = baseline.get(&measurement.key()).copied();
+            let mut diff = Diff { current: measurement, baseline, status: Status::New };
+            diff.status = match diff.change() {
+                None => Status::New,
+                Some(change) if change > threshold => Status::Regressed,
+                Some(change) if change < -threshold => Status::Improved,
+                Some(_) => Status::Unchanged,
+            };
+            diff
+        })
+        .collect()
+}
+
+// day     bench                 baseline     current   change  status
+// day-05  part1                 30.00 µs    45.00 µs   +50.0%  REGRESSED
+pub fn table(diffs: &[Diff]) -> String {
+    let rows: Vec<[String; 6]> = diffs
+        .iter()
+        .map(|diff| {
+            [
+                diff.current.day.clone(),
+                format!("{}/{}", diff.current.target, diff.current.bench),
+                diff.baseline
+                    .map(|m| format_duration(m.median_ns))
+                    .unwrap_or_else(|| "-".to_string()),
+                format_duration(diff.current.median_ns),
+                diff.change().map(format_change).unwrap_or_else(|| "-".to_string()),
+                diff.status.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["day", "bench", "baseline", "current", "change", "status"].map(String::from);
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {:>w4$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            row[5],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::Harness;
+
+    fn measurement(bench: &str, median_ns: f64) -> Measurement {
+        Measurement {
+            day: "day-05".to_string(),
+            target: "day-05-bench".to_string(),
+            bench: bench.to_string(),
+            harness: Harness::Divan,
+            median_ns,
+        }
+    }
+
+    fn run(results: Vec<Measurement>) -> Run {
+        Run { commit: "aaaaaaa".to_string(), dirty: false, timestamp: 0, results }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = run(vec![
+            measurement("part1", 30_000.0),
+            measurement("part2", 1_000_000.0),
+            measurement("unused", 1.0),
+        ]);
+        let current = run(vec![
+            measurement("part1", 45_000.0),
+            measurement("part2", 850_000.0),
+            measurement("parse", 10.0),
+        ]);
+        let statuses: Vec<Status> =
+            compare(&baseline, &current, 0.1).iter().map(|diff| diff.status).collect();
+        assert_eq!(vec![Status::Regressed, Status::Improved, Status::New], statuses);
+
+        // within the threshold either way
+        let current = run(vec![measurement("part1", 32_000.0), measurement("part2", 950_000.0)]);
+        assert!(compare(&baseline, &current, 0.1)
+            .iter()
+            .all(|diff| diff.status == Status::Unchanged));
+    }
+
+    #[test]
+    fn test_table() {
+        let baseline = run(vec![measurement("part1", 30_000.0)]);
+        let current = run(vec![measurement("part1", 45_000.0), measurement("part2", 1_000.0)]);
+        assert_eq!(
+            "day     bench               baseline   current  change  status
+day-05  day-05-bench/part1  30.00 µs  45.00 µs  +50.0%  REGRESSED
+day-05  day-05-bench/part2         -  1.000 µs       -  new
+",
+            table(&compare(&baseline, &current, 0.1))
+        );
+    }
+
+    #[test]
+    fn test_missing_baseline() {
+        let path = baseline_path(Path::new("/nonexistent"), "baseline");
+        assert!(matches!(
+            load_baseline(&path, "baseline"),
+            Err(AocError::MissingBaseline { .. })
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::json_error))]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("`{command}` failed with {status}")]
+    #[diagnostic(code(aoc::command_failed))]
+    CommandFailed {
+        command: String,
+        status: std::process::ExitStatus,
+    },
+
+    #[error("unexpected `cargo metadata` output: {0}")]
+    #[diagnostic(code(aoc::metadata))]
+    Metadata(String),
+
+    #[error("could not read a duration from `{0}`")]
+    #[diagnostic(code(aoc::bad_duration))]
+    BadDuration(String),
+
+    #[error("no benchmarks found for {0}")]
+    #[diagnostic(code(aoc::no_benches), help("day crates are named like `day-05`"))]
+    NoBenches(String),
+
+    #[error("there is no `{name}` benchmark baseline")]
+    #[diagnostic(
+        code(aoc::missing_baseline),
+        help("record one with `cargo run -p aoc -- bench --save-baseline {name}`")
+    )]
+    MissingBaseline { name: String },
+
+    #[error("{count} benchmark(s) regressed by more than {threshold}%")]
+    #[diagnostic(code(aoc::regression))]
+    Regression { count: usize, threshold: f64 },
+
+    #[error("there is no {0} in this workspace")]
+    #[diagnostic(code(aoc::unknown_day))]
+    UnknownDay(String),
+
+    #[error("there is no {0}, parts are `part1` and `part2`")]
+    #[diagnostic(code(aoc::unknown_part))]
+    UnknownPart(String),
+
+    #[error("{day} part {part} has no `{name}` variant")]
+    #[diagnostic(code(aoc::unknown_variant), help("the variants are: {available}"))]
+    UnknownVariant {
+        day: String,
+        part: usize,
+        name: String,
+        available: String,
+    },
+
+    #[error("the variants of {0} disagree")]
+    #[diagnostic(code(aoc::variants_disagree))]
+    VariantsDisagree(String),
+
+    #[error("panicked: {0}")]
+    #[diagnostic(
+        code(aoc::panicked),
+        help("run the day's binary with RUST_BACKTRACE=1 to see where")
+    )]
+    Panicked(String),
+
+    #[error("{0} part(s) failed")]
+    #[diagnostic(code(aoc::run_failed))]
+    RunFailed(usize),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::profiler))]
+    Profiler(#[from] pprof::Error),
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use aoc_harness::{Answer, Puzzle};
+
+use crate::custom_error::AocError;
+
+// A day's parsed input, whichever type the day parses into
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+// A day's `Puzzle` with the parsed type erased, so days that parse into
+// different types fit in one list
+pub trait Solver: Sync {
+    fn parse(&self, input: &str) -> miette::Result<Parsed>;
+    fn variants(&self, part: usize) -> Vec<&'static str>;
+    // `parsed` has to come from this puzzle's `parse`
+    fn solve(&self, part: usize, variant: &str, parsed: &Parsed) -> miette::Result<Answer>;
+}
+
+impl<P: Send + Sync + 'static> Solver for Puzzle<P> {
+    fn parse(&self, input: &str) -> miette::Result<Parsed> {
+        Ok(Arc::new((self.parse)(input)?))
+    }
+
+    fn variants(&self, part: usize) -> Vec<&'static str> {
+        self.parts[part - 1].iter().map(|variant| variant.name).collect()
+    }
+
+    fn solve(&self, part: usize, variant: &str, parsed: &Parsed) -> miette::Result<Answer> {
+        let variant = self.parts[part - 1]
+            .iter()
+            .find(|v| v.name == variant)
+            .ok_or_else(|| miette::miette!("no `{variant}` variant"))?;
+        let parsed = parsed.downcast_ref::<P>().ok_or_else(|| miette::miette!("parsed by another day"))?;
+        (variant.solve)(parsed)
+    }
+}
+
+// Everything the runner knows about a day, new days are added to `DAYS`.
+// Each part lists its variants, the first one is the part's default.
+pub struct Day {
+    pub name: &'static str,
+    pub generate: fn(usize, u64) -> String,
+    pub puzzle: &'static dyn Solver,
+}
+
+impl Day {
+    pub fn variants(&self, part: usize) -> Vec<&'static str> {
+        self.puzzle.variants(part)
+    }
+
+    // The part's default without a name
+    pub fn variant(&self, part: usize, name: Option<&str>) -> Option<&'static str> {
+        let variants = self.variants(part);
+        match name {
+            Some(name) => variants.into_iter().find(|variant| *variant == name),
+            None => variants.first().copied(),
+        }
+    }
+
+    // What the part's `process` does: parse, then solve with the variant
+    pub fn process(&self, part: usize, variant: &str, input: &str) -> miette::Result<Answer> {
+        self.puzzle.solve(part, variant, &self.puzzle.parse(input)?)
+    }
+
+    // The puzzle input, e.g. day-05/input1.txt
+    pub fn input(&self, part: usize) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(self.name)
+            .join(format!("input{part}.txt"))
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        name: "day-01",
+        generate: day_01::generate::generate,
+        puzzle: &day_01::PUZZLE,
+    },
+    Day {
+        name: "day-02",
+        generate: day_02::generate::generate,
+        puzzle: &day_02::PUZZLE,
+    },
+    Day {
+        name: "day-03",
+        generate: day_03::generate::generate,
+        puzzle: &day_03::PUZZLE,
+    },
+    Day {
+        name: "day-04",
+        generate: day_04::generate::generate,
+        puzzle: &day_04::PUZZLE,
+    },
+    Day {
+        name: "day-05",
+        generate: day_05::generate::generate,
+        puzzle: &day_05::PUZZLE,
+    },
+    Day {
+        name: "day-06",
+        generate: day_06::generate::generate,
+        puzzle: &day_06::PUZZLE,
+    },
+];
+
+// Accepts `day-05`, `05` and `5`
+pub fn find(name: &str) -> Result<&'static Day, AocError> {
+    let number = name.strip_prefix("day-").unwrap_or(name);
+    let name = match number.parse::<u32>() {
+        Ok(number) => format!("day-{number:02}"),
+        Err(_) => name.to_string(),
+    };
+    DAYS.iter()
+        .find(|day| day.name == name)
+        .ok_or(AocError::UnknownDay(name))
+}
+
+// Accepts `part1` and `1`
+pub fn parse_part(name: &str) -> Result<usize, AocError> {
+    match name.strip_prefix("part").unwrap_or(name) {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(AocError::UnknownPart(name.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::Context;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("day-05")]
+    #[case("05")]
+    #[case("5")]
+    fn test_find(#[case] name: &str) -> miette::Result<()> {
+        assert_eq!("day-05", find(name)?.name);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_unknown() {
+        assert!(matches!(find("day-26"), Err(AocError::UnknownDay(name)) if name == "day-26"));
+        assert!(find("tomorrow").is_err());
+    }
+
+    #[rstest]
+    #[case("part1", 1)]
+    #[case("2", 2)]
+    fn test_parse_part(#[case] name: &str, #[case] expected: usize) -> miette::Result<()> {
+        assert_eq!(expected, parse_part(name)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_part_unknown() {
+        assert!(matches!(parse_part("part3"), Err(AocError::UnknownPart(name)) if name == "part3"));
+    }
+
+    #[test]
+    fn test_parts() -> miette::Result<()> {
+        let day = find("day-06")?;
+        assert_eq!(288, day.process(1, "default", "Time:      7  15   30\nDistance:  9  40  200")?);
+        assert!(day.input(2).ends_with("day-06/input2.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_parsed() -> miette::Result<()> {
+        let day = find("day-06")?;
+        let parsed = day.puzzle.parse("Time:      7  15   30\nDistance:  9  40  200")?;
+        assert_eq!(288, day.puzzle.solve(1, "default", &parsed)?);
+        assert_eq!(71503, day.puzzle.solve(2, "default", &parsed)?);
+        // another day's parsed input is an error, not a panic
+        assert!(find("day-05")?.puzzle.solve(1, "default", &parsed).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_variant() -> miette::Result<()> {
+        let day = find("day-05")?;
+        assert_eq!(Some("interval"), day.variant(2, None));
+        assert_eq!(Some("naive"), day.variant(2, Some("naive")));
+        assert!(day.variant(2, Some("quantum")).is_none());
+        assert!(day.variant(1, Some("naive")).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_smallest_generated_inputs_parse() -> miette::Result<()> {
+        for day in DAYS {
+            day.puzzle.parse(&(day.generate)(1, 2023)).with_context(|| day.name)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_variant_names_are_unique() {
+        for day in DAYS {
+            for part in [1, 2] {
+                let mut names = day.variants(part);
+                assert!(!names.is_empty(), "{} part {part}", day.name);
+                names.sort();
+                names.dedup();
+                assert_eq!(day.variants(part).len(), names.len(), "{} part {part}", day.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Harness {
+    Divan,
+    Criterion,
+}
+
+impl std::fmt::Display for Harness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Harness::Divan => write!(f, "divan"),
+            Harness::Criterion => write!(f, "criterion"),
+        }
+    }
+}
+
+// One benchmark's median, e.g. day-05 / day-05-bench / part1
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: String,
+    pub target: String,
+    pub bench: String,
+    pub harness: Harness,
+    pub median_ns: f64,
+}
+
+impl Measurement {
+    pub fn key(&self) -> (&str, &str, &str) {
+        (&self.day, &self.target, &self.bench)
+    }
+}
+
+// Everything measured by one `aoc bench` invocation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub commit: String,
+    // The working tree had uncommitted changes
+    pub dirty: bool,
+    // Seconds since the unix epoch
+    pub timestamp: u64,
+    pub results: Vec<Measurement>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    // The latest median of every benchmark, along with the one recorded
+    // before it. Runs often only cover a single day, so "previous" is per
+    // benchmark rather than simply the run before the last one.
+    pub fn latest(&self) -> Vec<Row<'_>> {
+        let mut rows: BTreeMap<(&str, &str, &str), Row> = BTreeMap::new();
+        for run in &self.runs {
+            for measurement in &run.results {
+                rows.entry(measurement.key())
+                    .and_modify(|row| {
+                        row.previous = Some(row.current);
+                        row.current = (run, measurement);
+                    })
+                    .or_insert(Row { current: (run, measurement), previous: None });
+            }
+        }
+        rows.into_values().collect()
+    }
+}
+
+pub struct Row<'a> {
+    pub current: (&'a Run, &'a Measurement),
+    pub previous: Option<(&'a Run, &'a Measurement)>,
+}
+
+impl Row<'_> {
+    // Relative change of the median, 0.1 is 10% slower
+    pub fn change(&self) -> Option<f64> {
+        let (_, previous) = self.previous?;
+        Some(self.current.1.median_ns / previous.median_ns - 1.0)
+    }
+}
+
+// Formats nanoseconds the way divan does, e.g. 303.6 µs
+pub fn format_duration(ns: f64) -> String {
+    let (value, unit) = if ns < 1.0 {
+        (ns * 1000.0, "ps")
+    } else if ns < 1e3 {
+        (ns, "ns")
+    } else if ns < 1e6 {
+        (ns / 1e3, "µs")
+    } else if ns < 1e9 {
+        (ns / 1e6, "ms")
+    } else {
+        (ns / 1e9, "s")
+    };
+    let decimals = match value {
+        v if v < 10.0 => 3,
+        v if v < 100.0 => 2,
+        _ => 1,
+    };
+    format!("{value:.decimals$} {unit}")
+}
+
+pub fn format_change(change: f64) -> String {
+    format!("{:+.1}%", change * 100.0)
+}
+
+fn commit(run: &Run) -> String {
+    if run.dirty {
+        format!("{}-dirty", run.commit)
+    } else {
+        run.commit.clone()
+    }
+}
+
+// | Day | Bench | Harness | Median | Previous | Change | Commit |
+pub fn report(history: &History) -> String {
+    let mut out = String::from("# Benchmarks\n\n");
+    out.push_str("Generated by `cargo run -p aoc -- bench` from `benchmarks/history.json`, ");
+    out.push_str("do not edit by hand.\n\n");
+    out.push_str("| Day | Bench | Harness | Median | Previous | Change | Commit |\n");
+    out.push_str("|-----|-------|---------|-------:|---------:|-------:|--------|\n");
+    for row in history.latest() {
+        let (run, measurement) = row.current;
+        let bench = if measurement.target.ends_with("-bench") {
+            measurement.bench.clone()
+        } else {
+            format!("{} ({})", measurement.bench, measurement.target)
+        };
+        let previous = row
+            .previous
+            .map(|(_, m)| format_duration(m.median_ns))
+            .unwrap_or_else(|| "-".to_string());
+        let change = row.change().map(format_change).unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            measurement.day,
+            bench,
+            measurement.harness,
+            format_duration(measurement.median_ns),
+            previous,
+            change,
+            commit(run),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn measurement(bench: &str, median_ns: f64) -> Measurement {
+        Measurement {
+            day: "day-05".to_string(),
+            target: "day-05-bench".to_string(),
+            bench: bench.to_string(),
+            harness: Harness::Divan,
+            median_ns,
+        }
+    }
+
+    fn run(commit: &str, results: Vec<Measurement>) -> Run {
+        Run { commit: commit.to_string(), dirty: false, timestamp: 0, results }
+    }
+
+    #[rstest]
+    #[case(0.5, "500.0 ps")]
+    #[case(27.02, "27.02 ns")]
+    #[case(303_600.0, "303.6 µs")]
+    #[case(1_500_000.0, "1.500 ms")]
+    #[case(62_000_000_000.0, "62.00 s")]
+    fn test_format_duration(#[case] ns: f64, #[case] expected: &str) {
+        assert_eq!(expected, format_duration(ns));
+    }
+
+    #[test]
+    fn test_latest_compares_per_benchmark() {
+        let history = History {
+            runs: vec![
+                run("aaaaaaa", vec![measurement("part1", 100.0), measurement("part2", 1000.0)]),
+                run("bbbbbbb", vec![measurement("part1", 110.0)]),
+            ],
+        };
+        let rows = history.latest();
+        assert_eq!(2, rows.len());
+        assert_eq!("bbbbbbb", rows[0].current.0.commit);
+        assert!((rows[0].change().unwrap() - 0.1).abs() < 1e-9);
+        // part2 wasn't rerun, it keeps its old median and has nothing to compare to
+        assert_eq!("aaaaaaa", rows[1].current.0.commit);
+        assert_eq!(None, rows[1].change());
+    }
+
+    #[test]
+    fn test_report() {
+        let history = History {
+            runs: vec![
+                run("aaaaaaa", vec![measurement("part1", 30_000.0)]),
+                Run { dirty: true, ..run("bbbbbbb", vec![measurement("part1", 27_000.0)]) },
+            ],
+        };
+        let report = report(&history);
+        assert!(report.contains(
+            "| day-05 | part1 | divan | 27.00 µs | 30.00 µs | -10.0% | bbbbbbb-dirty |"
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let history = History { runs: vec![run("aaaaaaa", vec![measurement("part1", 1.0)])] };
+        let json = serde_json::to_string(&history).map_err(AocError::from)?;
+        assert!(json.contains(r#""harness":"divan""#));
+        assert_eq!(history, serde_json::from_str(&json).map_err(AocError::from)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,407 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{ArgAction, Parser, Subcommand};
+use miette::Context;
+
+mod bench;
+mod compare;
+mod custom_error;
+mod days;
+mod history;
+mod profile;
+mod run;
+mod timing;
+
+use aoc_harness::input::{with_mode, Mode};
+use custom_error::AocError;
+use history::{Harness, History};
+use timing::Timings;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code workspace tools")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    // Run the day benchmarks, record them in the history and regenerate the report
+    #[command(about = "Run benchmarks and update benchmarks/report.md")]
+    Bench {
+        #[arg(help = "Days to benchmark, e.g. day-05 (default: all)")]
+        days: Vec<String>,
+        #[arg(long, help = "Only run benchmarks whose name matches")]
+        filter: Option<String>,
+        #[arg(long, value_enum, help = "Only run divan or criterion benches")]
+        harness: Option<Harness>,
+        #[arg(long, default_value = "benchmarks/history.json")]
+        history: PathBuf,
+        #[arg(long, default_value = "benchmarks/report.md")]
+        report: PathBuf,
+        #[arg(long, value_name = "NAME", help = "Also store the results as a named baseline")]
+        save_baseline: Option<String>,
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with = "save_baseline",
+            help = "Compare against a saved baseline instead of recording, fails on regressions"
+        )]
+        compare: Option<String>,
+        #[arg(long, default_value_t = 10.0, help = "Allowed slowdown of a median in percent")]
+        threshold: f64,
+    },
+    #[command(about = "Generate a synthetic puzzle input")]
+    Gen {
+        #[arg(help = "Day to generate an input for, e.g. day-05 or 5")]
+        day: String,
+        // an empty input doesn't parse for most days, so 1 is the smallest size
+        #[arg(
+            long,
+            default_value_t = 1000,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+            help = "Number of lines, games, cards, seeds... depending on the day"
+        )]
+        size: usize,
+        #[arg(long, default_value_t = 2023)]
+        seed: u64,
+        #[arg(short, long, help = "Write to a file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+    #[command(about = "Run a day's parts and show where the time went")]
+    Run {
+        #[arg(required_unless_present = "all", help = "Day to run, e.g. day-05 or 5")]
+        day: Option<String>,
+        #[arg(requires = "day", help = "part1 or part2 (default: both)")]
+        part: Option<String>,
+        #[arg(long, conflicts_with = "day", help = "Run every day, the span timings are only shown with -v")]
+        all: bool,
+        #[arg(long, value_name = "NAME", help = "Run this variant instead of the default, e.g. naive")]
+        variant: Option<String>,
+        #[arg(long, help = "Print the results as JSON instead of tables")]
+        json: bool,
+        #[arg(long, help = "Fail on inputs with CRLF, a BOM or trailing whitespace instead of fixing them, and on inputs a day would otherwise work around")]
+        strict: bool,
+        #[arg(long, default_value = "30", value_parser = seconds, help = "Seconds a part may run before it's cancelled")]
+        timeout: Duration,
+        #[arg(
+            short,
+            long,
+            action = ArgAction::Count,
+            help = "Time debug spans too, -vv adds the per-line and per-item trace spans"
+        )]
+        verbose: u8,
+        #[arg(long, value_name = "PATH", help = "Write a Chrome trace, open it in ui.perfetto.dev")]
+        trace: Option<PathBuf>,
+    },
+    #[command(about = "Run every variant of the parts and check they give the same answers")]
+    Verify {
+        #[arg(help = "Day to verify, e.g. day-05 or 5 (default: all)")]
+        day: Option<String>,
+        #[arg(requires = "day", help = "part1 or part2 (default: both)")]
+        part: Option<String>,
+        #[arg(long, default_value = "30", value_parser = seconds, help = "Seconds a variant may run before it's cancelled")]
+        timeout: Duration,
+        #[arg(long, help = "Fail on inputs with CRLF, a BOM or trailing whitespace instead of fixing them, and on inputs a day would otherwise work around")]
+        strict: bool,
+    },
+    #[command(about = "Sample a part in-process and write a flamegraph and a pprof profile")]
+    Profile {
+        #[arg(help = "Day to profile, e.g. day-05 or 5")]
+        day: String,
+        #[arg(help = "part1 or part2")]
+        part: String,
+        #[arg(long, default_value = "5", value_parser = seconds, help = "How long to keep running the part")]
+        seconds: Duration,
+        #[arg(long, default_value_t = 1000, help = "Samples per second")]
+        frequency: i32,
+        #[arg(long, value_name = "NAME", help = "Profile this variant instead of the default")]
+        variant: Option<String>,
+        #[arg(long, help = "Profile on another input than the day's inputN.txt")]
+        input: Option<PathBuf>,
+        #[arg(long, default_value = "flamegraphs")]
+        output_dir: PathBuf,
+    },
+}
+
+#[tracing::instrument(skip_all)]
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    if !matches!(cli.command, Commands::Run { .. } | Commands::Verify { .. }) {
+        tracing_subscriber::fmt::init();
+    }
+
+    match cli.command {
+        Commands::Bench {
+            days,
+            filter,
+            harness,
+            history,
+            report,
+            save_baseline,
+            compare,
+            threshold,
+        } => {
+            let workspace = bench::workspace().context("read workspace metadata")?;
+
+            // Comparing is a check, it leaves the history and report alone
+            if let Some(name) = compare {
+                let path = compare::baseline_path(&workspace.root, &name);
+                let baseline = compare::load_baseline(&path, &name)
+                    .with_context(|| format!("load {}", path.display()))?;
+                let run = bench::run(&workspace, &days, harness, filter.as_deref())?;
+                let diffs = compare::compare(&baseline, &run, threshold / 100.0);
+                print!("{}", compare::table(&diffs));
+                let count = diffs
+                    .iter()
+                    .filter(|diff| diff.status == compare::Status::Regressed)
+                    .count();
+                if count > 0 {
+                    return Err(AocError::Regression { count, threshold }.into());
+                }
+                return Ok(());
+            }
+
+            let history_path = workspace.root.join(history);
+            let report_path = workspace.root.join(report);
+
+            let mut history = History::load(&history_path)
+                .with_context(|| format!("load {}", history_path.display()))?;
+            let run = bench::run(&workspace, &days, harness, filter.as_deref())?;
+            if let Some(name) = save_baseline {
+                let path = compare::baseline_path(&workspace.root, &name);
+                compare::save_baseline(&path, &run)
+                    .with_context(|| format!("save {}", path.display()))?;
+            }
+            history.runs.push(run);
+            history
+                .save(&history_path)
+                .with_context(|| format!("save {}", history_path.display()))?;
+
+            let report = history::report(&history);
+            std::fs::write(&report_path, &report)
+                .map_err(AocError::from)
+                .with_context(|| format!("write {}", report_path.display()))?;
+            print!("{report}");
+        }
+        Commands::Gen { day, size, seed, output } => {
+            let input = (days::find(&day)?.generate)(size, seed);
+            match output {
+                Some(path) => std::fs::write(&path, input)
+                    .map_err(AocError::from)
+                    .with_context(|| format!("write {}", path.display()))?,
+                None => print!("{input}"),
+            }
+        }
+        Commands::Run { day, part, all, variant, json, strict, timeout, verbose, trace } => {
+            let days: Vec<&days::Day> = match day {
+                Some(day) => vec![days::find(&day)?],
+                None => days::DAYS.iter().collect(),
+            };
+            let parts = match part {
+                Some(part) => vec![days::parse_part(&part)?],
+                None => vec![1, 2],
+            };
+
+            // Spans below the level are disabled at their callsite, so the
+            // hot-path trace spans cost nothing unless asked for
+            let level = match verbose {
+                0 => LevelFilter::INFO,
+                1 => LevelFilter::DEBUG,
+                _ => LevelFilter::TRACE,
+            };
+            let timings = Timings::default();
+            let (chrome, _flush) = match &trace {
+                Some(path) => {
+                    let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new().file(path).build();
+                    (Some(layer), Some(guard))
+                }
+                None => (None, None),
+            };
+            tracing_subscriber::registry()
+                .with(level)
+                .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+                .with(timings.clone())
+                .with(chrome)
+                .init();
+            run::quiet_todo_panics();
+
+            let mut runs = vec![];
+            for day in days {
+                let inputs = parts.iter().map(|&part| read_input(&day.input(part))).collect::<miette::Result<Vec<_>>>()?;
+                let jobs: Vec<run::Job> = parts
+                    .iter()
+                    .zip(&inputs)
+                    .map(|(&part, input)| run::Job { part, variant: variant.as_deref(), input: input.as_deref() })
+                    .collect();
+                runs.extend(with_mode(input_mode(strict), || {
+                    run::run_day(day, &jobs, timeout, &timings)
+                }));
+            }
+
+            if json {
+                let mut json = serde_json::to_string_pretty(&runs).map_err(AocError::from)?;
+                json.push('\n');
+                print!("{json}");
+            } else {
+                print!("{}", run::table(&runs));
+                if !all || verbose > 0 {
+                    println!();
+                    print!("{}", timings.table());
+                }
+                if let Some(path) = trace {
+                    println!("\nwrote {}", path.display());
+                }
+            }
+            let count = runs
+                .iter()
+                .filter(|run| matches!(run.outcome, run::Outcome::Failed { .. }))
+                .count();
+            if count > 0 {
+                return Err(AocError::RunFailed(count).into());
+            }
+        }
+        Commands::Verify { day, part, timeout, strict } => {
+            let days: Vec<&days::Day> = match day {
+                Some(day) => vec![days::find(&day)?],
+                None => days::DAYS.iter().collect(),
+            };
+            let parts = match part {
+                Some(part) => vec![days::parse_part(&part)?],
+                None => vec![1, 2],
+            };
+
+            let timings = Timings::default();
+            tracing_subscriber::registry()
+                .with(LevelFilter::INFO)
+                .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+                .with(timings.clone())
+                .init();
+            run::quiet_todo_panics();
+
+            let mut runs = vec![];
+            for day in days {
+                let inputs = parts.iter().map(|&part| read_input(&day.input(part))).collect::<miette::Result<Vec<_>>>()?;
+                let jobs: Vec<run::Job> = parts
+                    .iter()
+                    .zip(&inputs)
+                    .flat_map(|(&part, input)| {
+                        day.variants(part)
+                            .into_iter()
+                            .map(move |variant| run::Job { part, variant: Some(variant), input: input.as_deref() })
+                    })
+                    .collect();
+                runs.extend(with_mode(input_mode(strict), || {
+                    run::run_day(day, &jobs, timeout, &timings)
+                }));
+            }
+            print!("{}", run::table(&runs));
+
+            let disagreements = run::disagreements(&runs);
+            if !disagreements.is_empty() {
+                let parts: Vec<String> =
+                    disagreements.iter().map(|(day, part)| format!("{day} part {part}")).collect();
+                return Err(AocError::VariantsDisagree(parts.join(", ")).into());
+            }
+            let count = runs
+                .iter()
+                .filter(|run| matches!(run.outcome, run::Outcome::Failed { .. }))
+                .count();
+            if count > 0 {
+                return Err(AocError::RunFailed(count).into());
+            }
+        }
+        Commands::Profile { day, part, seconds, frequency, variant, input, output_dir } => {
+            let day = days::find(&day)?;
+            let part = days::parse_part(&part)?;
+            let name = day.variant(part, variant.as_deref()).ok_or_else(|| AocError::UnknownVariant {
+                day: day.name.to_string(),
+                part,
+                name: variant.clone().unwrap_or_default(),
+                available: day.variants(part).join(", "),
+            })?;
+            let input_path = input.unwrap_or_else(|| day.input(part));
+            let input = std::fs::read_to_string(&input_path)
+                .map_err(AocError::from)
+                .with_context(|| format!("read {}", input_path.display()))?;
+
+            let process = |input: &str| day.process(part, name, input);
+            let profile = profile::profile(process, &input, seconds, frequency)
+                .with_context(|| format!("profile {} part {part} ({name})", day.name))?;
+            let (svg, pprof) = profile::output_paths(&output_dir, day.name, part, variant.as_deref());
+            profile
+                .write(&svg, &pprof)
+                .with_context(|| format!("write {}", svg.display()))?;
+            println!(
+                "{} part {part} ({name}): {} runs in {:.1?}, wrote {} and {}",
+                day.name,
+                profile.iterations,
+                profile.elapsed,
+                svg.display(),
+                pprof.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+// A day's input, or None if there isn't one yet
+fn read_input(path: &std::path::Path) -> miette::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(input) => Ok(Some(input)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AocError::from(e)).with_context(|| format!("read {}", path.display())),
+    }
+}
+
+// A positive number of seconds, `Duration::from_secs_f64` panics on the rest
+fn seconds(arg: &str) -> Result<Duration, String> {
+    let seconds: f64 = arg.parse().map_err(|e| format!("{e}"))?;
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Err(format!("{arg} isn't a positive number of seconds"));
+    }
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("{e}"))
+}
+
+fn input_mode(strict: bool) -> Mode {
+    if strict {
+        Mode::Strict
+    } else {
+        Mode::Fix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gen_size() {
+        let size = |args: &[&str]| match Cli::try_parse_from(args) {
+            Ok(Cli { command: Commands::Gen { size, .. } }) => Some(size),
+            _ => None,
+        };
+        assert_eq!(Some(1000), size(&["aoc", "gen", "day-05"]));
+        assert_eq!(Some(1), size(&["aoc", "gen", "day-05", "--size", "1"]));
+        assert_eq!(None, size(&["aoc", "gen", "day-05", "--size", "0"]));
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(Ok(Duration::from_millis(1500)), seconds("1.5"));
+        assert_eq!(Ok(Duration::from_secs(30)), seconds("30"));
+        for arg in ["0", "-3", "NaN", "inf", "1e30", "soon"] {
+            assert!(seconds(arg).is_err(), "{arg}");
+        }
+        let timeout = |args: &[&str]| match Cli::try_parse_from(args) {
+            Ok(Cli { command: Commands::Run { timeout, .. } }) => Some(timeout),
+            _ => None,
+        };
+        assert_eq!(Some(Duration::from_secs(30)), timeout(&["aoc", "run", "day-05"]));
+        assert_eq!(None, timeout(&["aoc", "run", "day-05", "--timeout", "-1"]));
+    }
+}
@@ -0,0 +1,96 @@
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_harness::Answer;
+use pprof::protos::Message;
+
+use crate::custom_error::AocError;
+
+// Samples a part in this process with pprof, so neither perf nor root is
+// needed. The part runs over and over until `duration` is up, short parts
+// would barely get a sample otherwise.
+#[tracing::instrument(skip(part, input))]
+pub fn profile(
+    part: impl Fn(&str) -> miette::Result<Answer>,
+    input: &str,
+    duration: Duration,
+    frequency: i32,
+) -> miette::Result<Profile> {
+    // fail before sampling if the part doesn't work on this input
+    part(input)?;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(AocError::from)?;
+    let started = Instant::now();
+    let mut iterations = 0;
+    while started.elapsed() < duration {
+        black_box(part(black_box(input))?);
+        iterations += 1;
+    }
+    let report = guard.report().build().map_err(AocError::from)?;
+    Ok(Profile { report, iterations, elapsed: started.elapsed() })
+}
+
+pub struct Profile {
+    pub report: pprof::Report,
+    pub iterations: u64,
+    pub elapsed: Duration,
+}
+
+// flamegraphs/day-05--part1.svg and flamegraphs/day-05--part1.pb, other
+// variants than the default get their own, e.g. day-05--part2--naive.svg
+pub fn output_paths(dir: &Path, day: &str, part: usize, variant: Option<&str>) -> (PathBuf, PathBuf) {
+    let stem = match variant {
+        Some(variant) => format!("{day}--part{part}--{variant}"),
+        None => format!("{day}--part{part}"),
+    };
+    (dir.join(format!("{stem}.svg")), dir.join(format!("{stem}.pb")))
+}
+
+impl Profile {
+    // Writes the flamegraph and the pprof protobuf, which `go tool pprof`
+    // and speedscope can open
+    pub fn write(&self, svg: &Path, pprof: &Path) -> Result<(), AocError> {
+        for path in [svg, pprof] {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+        }
+        self.report.flamegraph(std::fs::File::create(svg)?)?;
+        std::fs::write(pprof, self.report.pprof()?.encode_to_vec())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_paths() {
+        let (svg, pprof) = output_paths(Path::new("flamegraphs"), "day-05", 2, None);
+        assert_eq!(Path::new("flamegraphs/day-05--part2.svg"), svg);
+        assert_eq!(Path::new("flamegraphs/day-05--part2.pb"), pprof);
+        let (svg, _) = output_paths(Path::new("flamegraphs"), "day-05", 2, Some("naive"));
+        assert_eq!(Path::new("flamegraphs/day-05--part2--naive.svg"), svg);
+    }
+
+    #[test]
+    fn test_profile_fails_on_bad_input() {
+        let part = |_: &str| Err(miette::miette!("bad input"));
+        assert!(profile(part, "", Duration::from_millis(10), 100).is_err());
+    }
+
+    #[test]
+    fn test_profile() -> miette::Result<()> {
+        let part = |input: &str| Ok(input.len().into());
+        let profile = profile(part, "input", Duration::from_millis(20), 100)?;
+        assert!(profile.iterations > 0);
+        assert!(profile.elapsed >= Duration::from_millis(20));
+        Ok(())
+    }
+}
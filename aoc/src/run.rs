@@ -0,0 +1,539 @@
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use aoc_harness::cancel::{with_token, CancellationToken};
+use aoc_harness::input::with_mode;
+use aoc_harness::Answer;
+use serde::Serialize;
+
+use crate::custom_error::AocError;
+use crate::days::{Day, Parsed};
+use crate::history::format_duration;
+use crate::timing::{Timing, Timings};
+
+// `todo!()` panics with this message, it's how unfinished days say so
+const TODO: &str = "not yet implemented";
+
+// How long a cancelled part gets to notice before the runner moves on
+const GRACE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Outcome {
+    Solved { answer: Answer },
+    Skipped { reason: String },
+    Failed { error: String },
+    TimedOut { limit_ns: f64 },
+}
+
+// One part's answer and how long it took, durations are in nanoseconds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartRun {
+    pub day: &'static str,
+    pub part: usize,
+    pub variant: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    // the input was already parsed for an earlier part or variant
+    pub parse_reused: bool,
+    pub parse_ns: Option<f64>,
+    pub solve_ns: Option<f64>,
+    pub total_ns: Option<f64>,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or_default()
+}
+
+// Keeps the default panic output for real panics but stays quiet about
+// `todo!()`, those parts are reported as skipped
+pub fn quiet_todo_panics() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !panic_message(info.payload()).starts_with(TODO) {
+            default(info);
+        }
+    }));
+}
+
+enum Finished<T> {
+    Returned(miette::Result<T>),
+    Panicked(Box<dyn Any + Send>),
+    TimedOut,
+}
+
+// Runs the job on a worker thread so a slow part can't hang the whole run.
+// Once it's over the limit its cancellation token is set, jobs that don't
+// check it are left running in the background.
+fn run_with_limit<T: Send + 'static>(
+    job: impl FnOnce() -> miette::Result<T> + Send + 'static,
+    limit: Duration,
+) -> Finished<T> {
+    let token = CancellationToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let worker = {
+        let token = token.clone();
+        // keep the part's spans under `run_day` in traces
+        let span = tracing::Span::current();
+        std::thread::Builder::new().name("part".to_string()).spawn(move || {
+            let result = span.in_scope(|| with_token(&token, || catch_unwind(AssertUnwindSafe(job))));
+            // nobody is listening any more if the job timed out
+            let _ = sender.send(result);
+        })
+    };
+    if let Err(e) = worker {
+        return Finished::Returned(Err(AocError::from(e).into()));
+    }
+
+    match receiver.recv_timeout(limit) {
+        Ok(Ok(result)) => Finished::Returned(result),
+        Ok(Err(payload)) => Finished::Panicked(payload),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            // don't let it slow down the next part if it stops in time
+            let _ = receiver.recv_timeout(GRACE);
+            Finished::TimedOut
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Finished::Returned(Err(miette::miette!("the worker thread exited without an answer")))
+        }
+    }
+}
+
+// What didn't give a value, as the run's outcome. `todo!()` is a skip.
+fn outcome<T>(finished: Finished<T>, limit: Duration) -> Result<T, Outcome> {
+    match finished {
+        Finished::Returned(Ok(value)) => Ok(value),
+        Finished::Returned(Err(report)) => Err(Outcome::Failed { error: format!("{report}") }),
+        Finished::Panicked(payload) if panic_message(payload.as_ref()).starts_with(TODO) => {
+            Err(Outcome::Skipped { reason: "not implemented".to_string() })
+        }
+        Finished::Panicked(payload) => {
+            let report = miette::Report::from(AocError::Panicked(panic_message(payload.as_ref()).to_string()));
+            Err(Outcome::Failed { error: format!("{report}") })
+        }
+        Finished::TimedOut => Err(Outcome::TimedOut { limit_ns: limit.as_secs_f64() * 1e9 }),
+    }
+}
+
+// One part to run: its variant, the default one without a name, and its
+// input if there is one yet
+#[derive(Debug, Clone, Copy)]
+pub struct Job<'a> {
+    pub part: usize,
+    pub variant: Option<&'a str>,
+    pub input: Option<&'a str>,
+}
+
+// Runs the jobs of a day in order. Each distinct input is parsed once and
+// the parsed input is reused by the other part and the other variants. Jobs
+// without an input or that variant, or still `todo!()`, are skipped, panics
+// and jobs over `limit` are reported instead of taking the runner down.
+// Inputs are parsed and solved in the caller's `input::Mode`. Parse and
+// solve times come from the `parse` and `solve` spans, so `timings` has to
+// be installed as a layer at the info level or lower.
+#[tracing::instrument(skip(day, jobs, timings), fields(day = day.name))]
+pub fn run_day<'a>(day: &Day, jobs: &[Job<'a>], limit: Duration, timings: &Timings) -> Vec<PartRun> {
+    let crate_name = day.name.replace('-', "_");
+    // the spans add up over runs, only count what this one adds
+    let phase = |module: &str, name| timings.get(&format!("{crate_name}::{module}"), name).unwrap_or_default();
+    let spent = |module: &str, name, before: Timing| {
+        let after = phase(module, name);
+        (after.calls > before.calls).then(|| (after.total - before.total).as_secs_f64() * 1e9)
+    };
+
+    // parse and solve run on worker threads, take the caller's mode along
+    let mode = aoc_harness::input::mode();
+    // keyed by the text itself, identical input files share their entry
+    let mut parsed: HashMap<&'a str, Result<Parsed, Outcome>> = HashMap::new();
+    let mut runs = vec![];
+    for job in jobs {
+        let part = job.part;
+        let mut run = PartRun {
+            day: day.name,
+            part,
+            variant: job.variant.unwrap_or_default().to_string(),
+            outcome: Outcome::Skipped { reason: "no input".to_string() },
+            parse_reused: false,
+            parse_ns: None,
+            solve_ns: None,
+            total_ns: None,
+        };
+        let Some(variant) = day.variant(part, job.variant) else {
+            run.outcome = Outcome::Skipped { reason: format!("no `{}` variant", run.variant) };
+            runs.push(run);
+            continue;
+        };
+        run.variant = variant.to_string();
+        let Some(input) = job.input.filter(|input| !input.trim().is_empty()) else {
+            runs.push(run);
+            continue;
+        };
+
+        let mut total = Duration::ZERO;
+        let input = match parsed.get(input) {
+            Some(input) => {
+                run.parse_reused = true;
+                input.clone()
+            }
+            None => {
+                let before = phase("parsed", "parse");
+                let puzzle = day.puzzle;
+                let text = input.to_string();
+                let started = Instant::now();
+                let finished = run_with_limit(move || with_mode(mode, || puzzle.parse(&text)), limit);
+                total += started.elapsed();
+                run.parse_ns = spent("parsed", "parse", before);
+                let parsed_input = outcome(finished, limit);
+                parsed.insert(input, parsed_input.clone());
+                parsed_input
+            }
+        };
+        match input {
+            Ok(input) => {
+                let before = phase(&format!("part{part}"), "solve");
+                let puzzle = day.puzzle;
+                let started = Instant::now();
+                let finished = run_with_limit(move || with_mode(mode, || puzzle.solve(part, variant, &input)), limit);
+                total += started.elapsed();
+                run.solve_ns = spent(&format!("part{part}"), "solve", before);
+                match outcome(finished, limit) {
+                    Ok(answer) => run.outcome = Outcome::Solved { answer },
+                    Err(outcome) => run.outcome = outcome,
+                }
+            }
+            Err(outcome) => run.outcome = outcome,
+        }
+        if !matches!(run.outcome, Outcome::Skipped { .. }) {
+            run.total_ns = Some(total.as_secs_f64() * 1e9);
+        }
+        runs.push(run);
+    }
+    runs
+}
+
+// The parts whose variants came up with different answers
+pub fn disagreements(runs: &[PartRun]) -> Vec<(&'static str, usize)> {
+    let mut answers: BTreeMap<(&'static str, usize), HashSet<&Answer>> = BTreeMap::new();
+    for run in runs {
+        if let Outcome::Solved { answer } = &run.outcome {
+            answers.entry((run.day, run.part)).or_default().insert(answer);
+        }
+    }
+    answers
+        .into_iter()
+        .filter(|(_, answers)| answers.len() > 1)
+        .map(|(part, _)| part)
+        .collect()
+}
+
+// day     part  variant   answer      parse      solve      total
+// day-05     1  default   35       31.20 µs   2.100 µs   35.00 µs
+// day-05     2  interval  46         reused   4.300 µs   4.500 µs
+// day-06     1  default   skipped: no input
+pub fn table(runs: &[PartRun]) -> String {
+    let duration = |ns: Option<f64>| ns.map(format_duration).unwrap_or_else(|| "-".to_string());
+    let rows: Vec<[String; 7]> = runs
+        .iter()
+        .map(|run| {
+            let answer = match &run.outcome {
+                Outcome::Solved { answer } => answer.to_string(),
+                Outcome::Skipped { reason } => format!("skipped: {reason}"),
+                Outcome::Failed { error } => format!("FAILED: {error}"),
+                Outcome::TimedOut { limit_ns } => format!("timed out after {}", format_duration(*limit_ns)),
+            };
+            [
+                run.day.to_string(),
+                run.part.to_string(),
+                run.variant.clone(),
+                answer,
+                if run.parse_reused { "reused".to_string() } else { duration(run.parse_ns) },
+                duration(run.solve_ns),
+                duration(run.total_ns),
+            ]
+        })
+        .collect();
+    let header = ["day", "part", "variant", "answer", "parse", "solve", "total"].map(String::from);
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = format!(
+            "{:<w0$}  {:>w1$}  {:<w2$}  {:<w3$}  {:>w4$}  {:>w5$}  {:>w6$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            row[5],
+            row[6],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+            w5 = widths[5],
+            w6 = widths[6],
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_harness::{Puzzle, Variant};
+
+    const PUZZLE: Puzzle<String> = Puzzle {
+        parse: |input| Ok(input.to_string()),
+        parts: [
+            &[
+                Variant { name: "len", solve: |input| Ok(input.len().into()) },
+                Variant { name: "chars", solve: |input| Ok(input.chars().count().into()) },
+            ],
+            &[Variant { name: "default", solve: |_| todo!() }],
+        ],
+    };
+    const DAY: Day = Day { name: "day-99", generate: |_, _| String::new(), puzzle: &PUZZLE };
+    const LIMIT: Duration = Duration::from_secs(10);
+
+    // DAY with another part 1
+    fn day_with(solve: fn(&String) -> miette::Result<Answer>) -> Day {
+        let part1: &'static [Variant<String>] = Box::leak(Box::new([Variant { name: "default", solve }]));
+        Day { puzzle: Box::leak(Box::new(Puzzle { parts: [part1, PUZZLE.parts[1]], ..PUZZLE })), ..DAY }
+    }
+
+    fn run_part(day: &Day, part: usize, variant: Option<&str>, input: Option<&str>, limit: Duration) -> PartRun {
+        run_day(day, &[Job { part, variant, input }], limit, &Timings::default()).remove(0)
+    }
+
+    fn solved(day: &'static str, variant: &str, answer: impl Into<Answer>) -> PartRun {
+        PartRun {
+            day,
+            part: 1,
+            variant: variant.to_string(),
+            outcome: Outcome::Solved { answer: answer.into() },
+            parse_reused: false,
+            parse_ns: None,
+            solve_ns: None,
+            total_ns: None,
+        }
+    }
+
+    #[test]
+    fn test_run_part() {
+        let run = run_part(&DAY, 1, None, Some("12345"), LIMIT);
+        assert_eq!(Outcome::Solved { answer: 5.into() }, run.outcome);
+        assert_eq!("len", run.variant);
+        assert!(run.total_ns.is_some());
+        // the fake day has no parse or solve spans
+        assert_eq!(None, run.parse_ns);
+    }
+
+    #[test]
+    fn test_run_part_skips() {
+        let skipped = |reason: &str| Outcome::Skipped { reason: reason.to_string() };
+        assert_eq!(skipped("no input"), run_part(&DAY, 1, None, None, LIMIT).outcome);
+        assert_eq!(skipped("no input"), run_part(&DAY, 1, None, Some("\n"), LIMIT).outcome);
+        assert_eq!(skipped("not implemented"), run_part(&DAY, 2, None, Some("input"), LIMIT).outcome);
+        let run = run_part(&DAY, 2, Some("chars"), Some("input"), LIMIT);
+        assert_eq!(skipped("no `chars` variant"), run.outcome);
+        assert_eq!("chars", run.variant);
+    }
+
+    #[test]
+    fn test_run_part_variant() {
+        let run = run_part(&DAY, 1, Some("chars"), Some("é"), LIMIT);
+        assert_eq!(Outcome::Solved { answer: 1.into() }, run.outcome);
+        assert_eq!("chars", run.variant);
+    }
+
+    #[test]
+    fn test_run_part_fails() {
+        let day = day_with(|_| Err(miette::miette!("bad input")));
+        let run = run_part(&day, 1, None, Some("input"), LIMIT);
+        assert_eq!(Outcome::Failed { error: "bad input".to_string() }, run.outcome);
+    }
+
+    #[test]
+    fn test_run_part_catches_panics() {
+        let day = day_with(|_| panic!("index out of bounds"));
+        let run = run_part(&day, 1, None, Some("input"), LIMIT);
+        assert_eq!(Outcome::Failed { error: "panicked: index out of bounds".to_string() }, run.outcome);
+    }
+
+    #[test]
+    fn test_run_part_times_out() {
+        // stops as soon as it's cancelled
+        let day = day_with(|_| loop {
+            if aoc_harness::cancel::is_cancelled() {
+                return Err(miette::miette!("cancelled"));
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        });
+        let started = Instant::now();
+        let run = run_part(&day, 1, None, Some("input"), Duration::from_millis(50));
+        assert_eq!(Outcome::TimedOut { limit_ns: 50e6 }, run.outcome);
+        assert!(started.elapsed() < GRACE);
+    }
+
+    #[test]
+    fn test_run_day_parses_once() {
+        let jobs = [
+            Job { part: 1, variant: None, input: Some("é") },
+            Job { part: 1, variant: Some("chars"), input: Some("é") },
+            Job { part: 2, variant: None, input: Some("é") },
+            Job { part: 1, variant: None, input: Some("other") },
+        ];
+        let runs = run_day(&DAY, &jobs, LIMIT, &Timings::default());
+        let reused: Vec<bool> = runs.iter().map(|run| run.parse_reused).collect();
+        assert_eq!(vec![false, true, true, false], reused);
+        assert_eq!(Outcome::Solved { answer: 2.into() }, runs[0].outcome);
+        assert_eq!(Outcome::Solved { answer: 1.into() }, runs[1].outcome);
+        assert_eq!(Outcome::Solved { answer: 5.into() }, runs[3].outcome);
+    }
+
+    #[test]
+    fn test_run_day_parse_fails() {
+        let day = Day { puzzle: &Puzzle { parse: |_| Err(miette::miette!("bad input")), ..PUZZLE }, ..DAY };
+        let jobs = [Job { part: 1, variant: None, input: Some("input") }, Job { part: 2, variant: None, input: Some("input") }];
+        let runs = run_day(&day, &jobs, LIMIT, &Timings::default());
+        // the failure is reported for both parts without parsing again
+        for run in &runs {
+            assert_eq!(Outcome::Failed { error: "bad input".to_string() }, run.outcome);
+        }
+        assert!(runs[1].parse_reused);
+    }
+
+    #[test]
+    fn test_run_day_strict() {
+        use aoc_harness::input::{with_mode, Mode};
+
+        let day = Day {
+            puzzle: &Puzzle { parse: |input| Ok(aoc_harness::input::normalise(input)?.into_owned()), ..PUZZLE },
+            ..DAY
+        };
+        let jobs = [Job { part: 1, variant: None, input: Some("ab\r\n") }];
+        let fixed = run_day(&day, &jobs, LIMIT, &Timings::default());
+        assert_eq!(Outcome::Solved { answer: 3.into() }, fixed[0].outcome);
+        let strict = with_mode(Mode::Strict, || run_day(&day, &jobs, LIMIT, &Timings::default()));
+        assert!(
+            matches!(&strict[0].outcome, Outcome::Failed { error } if error.contains("line 1 ends in CRLF")),
+            "{:?}",
+            strict[0].outcome
+        );
+
+        // days can be strict about more than the input's whitespace
+        let day = day_with(|input| match aoc_harness::input::mode() {
+            Mode::Fix => Ok(input.len().into()),
+            Mode::Strict => Err(miette::miette!("copies past the end")),
+        });
+        let jobs = [Job { part: 1, variant: None, input: Some("ab\n") }];
+        let fixed = run_day(&day, &jobs, LIMIT, &Timings::default());
+        assert_eq!(Outcome::Solved { answer: 3.into() }, fixed[0].outcome);
+        let strict = with_mode(Mode::Strict, || run_day(&day, &jobs, LIMIT, &Timings::default()));
+        assert_eq!(Outcome::Failed { error: "copies past the end".to_string() }, strict[0].outcome);
+    }
+
+    #[test]
+    fn test_table() {
+        let runs = vec![
+            PartRun {
+                day: "day-05",
+                part: 1,
+                variant: "default".to_string(),
+                outcome: Outcome::Solved { answer: 35.into() },
+                parse_reused: false,
+                parse_ns: Some(31_200.0),
+                solve_ns: Some(2_100.0),
+                total_ns: Some(35_000.0),
+            },
+            PartRun {
+                day: "day-05",
+                part: 2,
+                variant: "interval".to_string(),
+                outcome: Outcome::Solved { answer: 46.into() },
+                parse_reused: true,
+                parse_ns: None,
+                solve_ns: Some(4_300.0),
+                total_ns: Some(4_500.0),
+            },
+            PartRun {
+                day: "day-06",
+                part: 2,
+                variant: "default".to_string(),
+                outcome: Outcome::Skipped { reason: "no input".to_string() },
+                parse_reused: false,
+                parse_ns: None,
+                solve_ns: None,
+                total_ns: None,
+            },
+        ];
+        assert_eq!(
+            "day     part  variant   answer                parse     solve     total
+day-05     1  default   35                 31.20 µs  2.100 µs  35.00 µs
+day-05     2  interval  46                   reused  4.300 µs  4.500 µs
+day-06     2  default   skipped: no input         -         -         -
+",
+            table(&runs)
+        );
+    }
+
+    #[test]
+    fn test_table_timed_out() {
+        let run = PartRun {
+            day: "day-05",
+            part: 2,
+            variant: "naive".to_string(),
+            outcome: Outcome::TimedOut { limit_ns: 30e9 },
+            parse_reused: false,
+            parse_ns: None,
+            solve_ns: None,
+            total_ns: Some(30.5e9),
+        };
+        let table = table(&[run]);
+        let row = table.lines().nth(1).unwrap();
+        assert!(row.contains("  timed out after 30.00 s  "), "{row}");
+        assert!(row.ends_with("  30.50 s"), "{row}");
+    }
+
+    #[test]
+    fn test_json() -> miette::Result<()> {
+        let run = PartRun { total_ns: Some(1.0), ..solved("day-05", "default", 35u32) };
+        let json = serde_json::to_value(&run).map_err(crate::custom_error::AocError::from)?;
+        assert_eq!("solved", json["status"]);
+        assert_eq!(35, json["answer"]);
+        assert_eq!("default", json["variant"]);
+        assert_eq!(false, json["parse_reused"]);
+        assert_eq!(1.0, json["total_ns"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disagreements() {
+        let mut runs = vec![
+            solved("day-02", "hashmap", 8u32),
+            solved("day-02", "match", "8"),
+            solved("day-05", "interval", 46u64),
+            solved("day-05", "naive", 45u64),
+        ];
+        // only solved variants are compared
+        runs.push(PartRun { outcome: Outcome::Failed { error: "bad input".to_string() }, ..solved("day-02", "array", "") });
+        assert_eq!(vec![("day-05", 1)], disagreements(&runs));
+    }
+}
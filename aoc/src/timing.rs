@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::history::format_duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub calls: u64,
+    pub total: Duration,
+}
+
+// Time spent inside spans, added up by target and span name, e.g.
+// `day_05::part1` / `parse`. Only the time a span is entered counts, so a
+// span waiting on its children still includes them but idle time doesn't.
+// A span entered on several threads at once, like `run_day` around its
+// workers, counts that time once, and it's one call when it closes.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    spans: Arc<Mutex<BTreeMap<(&'static str, &'static str), Timing>>>,
+}
+
+// How long a span has been entered so far, kept in the span's extensions.
+// `depth` counts the entries that haven't exited yet over all threads.
+#[derive(Default)]
+struct Busy {
+    depth: usize,
+    since: Option<Instant>,
+    total: Duration,
+}
+
+impl Timings {
+    pub fn get(&self, target: &str, name: &str) -> Option<Timing> {
+        self.lock().get(&(target, name)).copied()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<(&'static str, &'static str), Timing>> {
+        self.spans.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // span                        calls     total      mean
+    // day_05::part1::parse            1  31.20 µs  31.20 µs
+    pub fn table(&self) -> String {
+        let mut spans: Vec<_> = self
+            .lock()
+            .iter()
+            .map(|(&(target, name), &timing)| (format!("{target}::{name}"), timing))
+            .collect();
+        spans.sort_by(|a, b| b.1.total.cmp(&a.1.total).then_with(|| a.0.cmp(&b.0)));
+
+        let width = spans.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0).max(4);
+        let mut out = format!("{:<width$}  {:>8}  {:>10}  {:>10}\n", "span", "calls", "total", "mean");
+        for (name, timing) in spans {
+            let total = timing.total.as_secs_f64() * 1e9;
+            out.push_str(&format!(
+                "{:<width$}  {:>8}  {:>10}  {:>10}\n",
+                name,
+                timing.calls,
+                format_duration(total),
+                format_duration(total / timing.calls as f64),
+            ));
+        }
+        out
+    }
+}
+
+impl<S> Layer<S> for Timings
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if extensions.get_mut::<Busy>().is_none() {
+            extensions.insert(Busy::default());
+        }
+        let busy = extensions.get_mut::<Busy>().expect("just inserted");
+        if busy.depth == 0 {
+            busy.since = Some(Instant::now());
+        }
+        busy.depth += 1;
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(busy) = extensions.get_mut::<Busy>() else {
+            return;
+        };
+        busy.depth = busy.depth.saturating_sub(1);
+        if busy.depth == 0 {
+            if let Some(since) = busy.since.take() {
+                busy.total += since.elapsed();
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        // spans that were never entered didn't take any time
+        let Some(busy) = span.extensions_mut().remove::<Busy>() else {
+            return;
+        };
+        let metadata = span.metadata();
+        let mut spans = self.lock();
+        let timing = spans.entry((metadata.target(), metadata.name())).or_default();
+        timing.calls += 1;
+        timing.total += busy.total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[tracing::instrument(level = "info")]
+    fn outer() {
+        for _ in 0..3 {
+            inner();
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn inner() {}
+
+    #[test]
+    fn test_timings() {
+        let timings = Timings::default();
+        let subscriber = tracing_subscriber::registry().with(timings.clone());
+        tracing::subscriber::with_default(subscriber, outer);
+
+        let target = module_path!();
+        assert_eq!(1, timings.get(target, "outer").unwrap().calls);
+        let inner = timings.get(target, "inner").unwrap();
+        assert_eq!(3, inner.calls);
+        assert!(timings.get(target, "outer").unwrap().total >= inner.total);
+
+        let table = timings.table();
+        assert!(table.starts_with("span "));
+        assert!(table.contains("aoc::timing::tests::inner"));
+    }
+
+    #[test]
+    fn test_entered_on_several_threads() {
+        let timings = Timings::default();
+        // the registry closes spans through the thread's default subscriber,
+        // so the workers need it too, like the runner's global one
+        let dispatch = tracing::Dispatch::new(tracing_subscriber::registry().with(timings.clone()));
+        let started = Instant::now();
+        tracing::dispatcher::with_default(&dispatch, || {
+            let span = tracing::info_span!("shared");
+            let both_inside = std::sync::Barrier::new(2);
+            span.in_scope(|| {
+                std::thread::scope(|scope| {
+                    for _ in 0..2 {
+                        scope.spawn(|| {
+                            tracing::dispatcher::with_default(&dispatch, || {
+                                span.in_scope(|| {
+                                    both_inside.wait();
+                                    std::thread::sleep(Duration::from_millis(50));
+                                })
+                            })
+                        });
+                    }
+                });
+            });
+        });
+        let elapsed = started.elapsed();
+
+        let shared = timings.get(module_path!(), "shared").unwrap();
+        assert_eq!(1, shared.calls);
+        // the overlapping entries on the workers count once
+        assert!(shared.total >= Duration::from_millis(50), "{shared:?}");
+        assert!(shared.total <= elapsed, "{shared:?} in {elapsed:?}");
+    }
+
+    #[test]
+    fn test_level_filter() {
+        let timings = Timings::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_subscriber::filter::LevelFilter::INFO)
+            .with(timings.clone());
+        tracing::subscriber::with_default(subscriber, outer);
+
+        // trace spans are filtered out, hot paths cost nothing by default
+        assert_eq!(None, timings.get(module_path!(), "inner"));
+        assert!(timings.get(module_path!(), "outer").is_some());
+    }
+}
@@ -1,4 +1,5 @@
-use {{crate_name}}::part1::process;
+use {{crate_name}}::parsed::parse;
+use {{crate_name}}::part1::solve;
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -10,11 +11,33 @@ fn main() -> miette::Result<()> {
     #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-    #[cfg(not(feature = "dhat-heap"))]
+    // Spans go to Tracy instead of stdout, see `just tracy`
+    #[cfg(feature = "tracy")]
+    {
+        use tracing_subscriber::layer::SubscriberExt;
+        tracing::subscriber::set_global_default(
+            tracing_subscriber::registry().with(tracing_tracy::TracyLayer::default()),
+        )
+        .expect("install the tracy layer");
+    }
+
+    #[cfg(not(any(feature = "dhat-heap", feature = "tracy")))]
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let parsed = {
+        #[cfg(feature = "tracy")]
+        let _frame = tracy_client::non_continuous_frame!("part1 parse");
+        parse(file).context("parse part 1")?
+    };
+    let result = {
+        #[cfg(feature = "tracy")]
+        let _frame = tracy_client::non_continuous_frame!("part1 solve");
+        solve(&parsed).context("solve part 1")?
+    };
+    #[cfg(feature = "tracy")]
+    tracy_client::frame_mark();
+
     println!("{}", result);
     Ok(())
 }
\ No newline at end of file
@@ -7,4 +7,8 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
 }
\ No newline at end of file
@@ -0,0 +1,23 @@
+use fastrand::Rng;
+
+// `size` lines of puzzle input, made up from `seed` so they can be reproduced
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    for _ in 0..size {
+        input.push_str(&rng.u32(..).to_string());
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
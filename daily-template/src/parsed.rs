@@ -0,0 +1,12 @@
+use crate::custom_error::AocError;
+
+// What both parts solve, the runner parses an input once for the two of them
+pub type Parsed = Vec<String>;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    Ok(input.lines().map(String::from).collect())
+}
\ No newline at end of file
@@ -1,21 +1,44 @@
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(_lines))]
+pub fn solve(
+    _lines: &[String],
+) -> miette::Result<Answer, AocError> {
+    todo!("{{project-name}} - part 1");
+}
+
+#[tracing::instrument(skip(input))]
 pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+    input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(input)?)
 }
 
+// Add other ways of solving the part here, `aoc verify` checks they agree
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
-        Ok(())
-    }
+    aoc_harness::example_tests!(
+        process,
+        "examples/part1/*.txt",
+        ignore = "fill in examples/part1/example.txt, then remove this line"
+    );
+    aoc_harness::normalisation_tests!(
+        process,
+        "examples/part1/*.txt",
+        ignore = "fill in examples/part1/example.txt, then remove this line"
+    );
+    aoc_harness::variant_tests!(
+        parse,
+        VARIANTS,
+        "examples/part1/*.txt",
+        ignore = "fill in examples/part1/example.txt, then remove this line"
+    );
 }
\ No newline at end of file
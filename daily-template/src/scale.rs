@@ -0,0 +1,12 @@
+// The input repeated `factor` times, adjust this when lines depend on each
+// other (ids, sections, grids...)
+pub fn scaled(input: &str, factor: usize) -> String {
+    let mut scaled = String::with_capacity((input.len() + 1) * factor);
+    for _ in 0..factor {
+        for line in input.lines() {
+            scaled.push_str(line);
+            scaled.push('\n');
+        }
+    }
+    scaled
+}
\ No newline at end of file
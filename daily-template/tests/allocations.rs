@@ -0,0 +1,25 @@
+// dhat sees every allocation in the process, so the budgets are checked from
+// their own test binary with dhat as the global allocator
+use std::path::{Path, PathBuf};
+
+use aoc_harness::{assert_allocations, Budget};
+use {{crate_name}}::{part1, part2};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn example(part: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join(part).join("example.txt")
+}
+
+#[test]
+#[ignore = "set a budget a little above what part1 allocates on its example, then remove this line"]
+fn part1_allocations() -> miette::Result<()> {
+    assert_allocations(&example("part1"), part1::process, Budget { blocks: 0, peak_bytes: 0 })
+}
+
+#[test]
+#[ignore = "set a budget a little above what part2 allocates on its example, then remove this line"]
+fn part2_allocations() -> miette::Result<()> {
+    assert_allocations(&example("part2"), part2::process, Budget { blocks: 0, peak_bytes: 0 })
+}
@@ -0,0 +1,4 @@
+fn main() {
+    // Pick up new example files without touching any Rust
+    println!("cargo:rerun-if-changed=examples");
+}
\ No newline at end of file
@@ -7,4 +7,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
+
+    #[error("line {line} has no digits")]
+    #[diagnostic(code(aoc::no_digits))]
+    NoDigits { line: usize },
 }
\ No newline at end of file
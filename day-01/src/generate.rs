@@ -0,0 +1,50 @@
+use fastrand::Rng;
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// `size` calibration lines of letters, digits and spelled out digits. Every
+// line has at least one real digit so part 1 can read it too.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    for _ in 0..size {
+        let length = rng.usize(1..=40);
+        let digit_at = rng.usize(0..length);
+        let mut line = String::new();
+        for i in 0..length {
+            if i == digit_at {
+                line.push(rng.char('1'..='9'));
+                continue;
+            }
+            match rng.u8(0..10) {
+                0 => line.push(rng.char('1'..='9')),
+                1 => line.push_str(WORDS[rng.usize(0..WORDS.len())]),
+                _ => line.push(rng.char('a'..='z')),
+            }
+        }
+        input.push_str(&line);
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated_input_solves() -> miette::Result<()> {
+        let input = generate(100, 1);
+        assert_eq!(100, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
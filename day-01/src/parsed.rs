@@ -0,0 +1,25 @@
+use crate::custom_error::AocError;
+
+// The lines as they are, each part picks out the digits it counts: part 1
+// only the ones written as digits, part 2 the spelled out ones too. Lines
+// without any are only an error for the part that has to read them.
+pub type Parsed = Vec<String>;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    Ok(input.lines().map(String::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        assert_eq!(vec!["1abc2", "xtwone3four", "none"], parse("1abc2\r\nxtwone3four\r\nnone")?);
+        Ok(())
+    }
+}
\ No newline at end of file
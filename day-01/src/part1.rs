@@ -1,37 +1,51 @@
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
 
-#[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
+// This runs for every line, so it's only traced at the trace level (`aoc run -vv`)
+#[tracing::instrument(level = "trace")]
+fn digits(line: &str) -> Vec<u32> {
+    line.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+#[tracing::instrument(skip(lines))]
+pub fn solve(
+    lines: &[String],
+) -> miette::Result<Answer, AocError> {
     let mut sum = 0;
-    for line in _input.lines() {
-
-        let mut digits: Vec<u32> = vec![];
-        for c in line.chars() {
-            // check if c is a digit
-            if c.is_digit(10) {
-                digits.push(c.to_digit(10).unwrap() as u32);
-            }
+    for (i, line) in lines.iter().enumerate() {
+        let digits = digits(line);
+        if digits.is_empty() {
+            return Err(AocError::NoDigits { line: i + 1 });
         }
         let number = format!("{}{}", digits[0], digits[digits.len() - 1]);
         sum += number.parse::<u32>().unwrap();
     }
 
-    return Ok(sum.to_string());
+    return Ok(sum.into());
+}
+
+#[tracing::instrument(skip(_input))]
+pub fn process(
+    _input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(_input)?)
 }
 
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    aoc_harness::example_tests!(process, "examples/part1/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part1/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part1/*.txt");
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-        assert_eq!("142", process(input)?);
-        Ok(())
+    fn test_line_without_digits() {
+        assert!(matches!(process("1abc2\nabc"), Err(AocError::NoDigits { line: 2 })));
     }
 }
\ No newline at end of file
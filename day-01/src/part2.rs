@@ -1,19 +1,42 @@
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
 
-#[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
+#[tracing::instrument(skip(lines))]
+pub fn solve(
+    lines: &[String],
+) -> miette::Result<Answer, AocError> {
     let mut sum = 0;
-    for line in _input.lines() {
-        sum += process_line(line)
+    for (i, line) in lines.iter().enumerate() {
+        let digits = spelled_digits(line);
+        if digits.is_empty() {
+            return Err(AocError::NoDigits { line: i + 1 });
+        }
+        sum += calibration_value(&digits)
     }
 
-    return Ok(sum.to_string());
+    return Ok(sum.into());
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(_input))]
+pub fn process(
+    _input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(_input)?)
+}
+
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
+
+#[cfg(test)]
 fn process_line(line: &str) -> u32 {
+    calibration_value(&spelled_digits(line))
+}
+
+// This runs for every line, so it's only traced at the trace level (`aoc run -vv`)
+#[tracing::instrument(level = "trace")]
+fn spelled_digits(line: &str) -> Vec<u32> {
     let mut i = 0;
     // Eat up the line character by character and check if it starts with a number. If so, return the number.
     let line_iter = std::iter::from_fn(move || {
@@ -40,36 +63,41 @@ fn process_line(line: &str) -> u32 {
             let result = new_line.chars().next();
             result
         };
-        i += 1;
+        // step over whole characters, the input isn't necessarily ascii
+        i += new_line.chars().next().map_or(1, char::len_utf8);
         result
     });
     let mut digits: Vec<u32> = vec![];
     for c in line_iter {
         // check if c is a digit
-        if c.is_digit(10) {
+        if c.is_ascii_digit() {
             digits.push(c.to_digit(10).unwrap());
         }
     }
+    digits
+}
+
+fn calibration_value(digits: &[u32]) -> u32 {
     let number = format!("{}{}", digits[0], digits[digits.len() - 1]);
-    return number.parse::<u32>().unwrap();
+    number.parse::<u32>().unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
+
+    aoc_harness::example_tests!(process, "examples/part2/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part2/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part2/*.txt");
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        assert_eq!("281", process(input)?);
-        Ok(())
+    fn test_line_without_digits() {
+        // spelled out digits count in part 2
+        assert_eq!(11, process("one").unwrap());
+        assert!(matches!(process("1abc2\nabc"), Err(AocError::NoDigits { line: 2 })));
     }
+
     #[rstest]
     #[case("two1nine", 29)]
     #[case("eightwothree", 83)]
@@ -0,0 +1,21 @@
+// The input repeated `factor` times, every calibration line stands on its own
+pub fn scaled(input: &str, factor: usize) -> String {
+    let mut scaled = String::with_capacity((input.len() + 1) * factor);
+    for _ in 0..factor {
+        for line in input.lines() {
+            scaled.push_str(line);
+            scaled.push('\n');
+        }
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled() {
+        assert_eq!("1abc2\npqr3stu8vwx\n1abc2\npqr3stu8vwx\n", scaled("1abc2\npqr3stu8vwx", 2));
+    }
+}
\ No newline at end of file
@@ -7,4 +7,19 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
+
+    #[error("failed to parse games: {0}")]
+    #[diagnostic(
+        code(aoc::parse_error),
+        help("games look like `Game 1: 3 blue, 4 red; 1 red, 2 green`")
+    )]
+    ParseError(String),
+
+    #[error("the sum of the powers doesn't fit in a u64 from game {game} on")]
+    #[diagnostic(code(aoc::power_overflow))]
+    PowerOverflow { game: u32 },
+}
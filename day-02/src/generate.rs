@@ -0,0 +1,61 @@
+use fastrand::Rng;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+// `size` games of one to six rounds, every round shows up to one handful of
+// each color
+pub fn generate(size: usize, seed: u64) -> String {
+    generate_with_counts(size, seed, 20)
+}
+
+// Same as `generate` with up to `max_count` cubes of a color at once. Counts
+// past 2^21 make the sum of the powers overflow a u64, past 2^22 a single
+// game's power does.
+pub fn generate_with_counts(size: usize, seed: u64, max_count: u32) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    for id in 1..=size {
+        let rounds: Vec<String> = (0..rng.usize(1..=6))
+            .map(|_| {
+                let mut colors = COLORS;
+                rng.shuffle(&mut colors);
+                colors[..rng.usize(1..=3)]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.u32(1..=max_count)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        input.push_str(&format!("Game {id}: {}\n", rounds.join("; ")));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated_input_solves() -> miette::Result<()> {
+        let input = generate(100, 1);
+        assert_eq!(100, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_counts() {
+        let input = generate_with_counts(50, 1, u32::MAX);
+        assert!(matches!(part2::process(&input), Err(crate::custom_error::AocError::PowerOverflow { .. })));
+        // still the same games
+        assert_eq!(generate(50, 1).lines().count(), input.lines().count());
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
@@ -0,0 +1,179 @@
+use nom::{Finish, IResult};
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, digit1, line_ending};
+use nom::multi::separated_list1;
+use nom::combinator::map_res;
+use nom::sequence::{preceded, separated_pair};
+use crate::custom_error::AocError;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub(crate) struct Cube {
+    pub(crate) color: String,
+    pub(crate) quantity: u32,
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub(crate) struct Round {
+    pub(crate) cubes: Vec<Cube>,
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Game {
+    pub(crate) number: u32,
+    pub(crate) rounds: Vec<Round>,
+}
+
+// Part 1 checks the games against the bag, part 2 finds the smallest bag
+pub type Parsed = Vec<Game>;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    let (_, games) = parse_games(&input)
+        .finish()
+        .map_err(|e| AocError::ParseError(e.to_string()))?;
+    Ok(games)
+}
+
+// input: 3 blue
+fn parse_cube(input: &str) -> IResult<&str, Cube> {
+    let (input, (quantity, color)) = separated_pair(
+        map_res(digit1, |d: &str| d.parse::<u32>()), tag(" "), alpha1
+    )(input)?;
+    Ok((input, Cube { color: color.to_string(), quantity }))
+}
+
+// input: 1 red, 2 green, 6 blue
+fn parse_round(input: &str) -> IResult<&str, Round> {
+    let (input, cubes) = separated_list1(tag(", "), parse_cube)(input)?;
+    Ok((input, Round { cubes }))
+}
+
+// input: Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+fn parse_game(input: &str) -> IResult<&str, Game> {
+    let (input, (number, rounds)) = separated_pair(
+        preceded(tag("Game "), map_res(digit1, |d: &str| d.parse::<u32>())),
+        tag(": "),
+        separated_list1(tag("; "), parse_round)
+    )(input)?;
+    Ok((input, Game { number, rounds }))
+}
+
+pub(crate) fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
+    let (input, games) = separated_list1(line_ending, parse_game)(input)?;
+    Ok((input, games))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cube() -> miette::Result<()> {
+        let input = "3 blue";
+        let result = parse_cube(input);
+        assert_eq!(result, Ok(("", Cube { color: "blue".to_string(), quantity: 3 })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_round() -> miette::Result<()> {
+        let input = "3 blue, 4 red";
+        let result = parse_round(input);
+        assert_eq!(result, Ok(("", Round { cubes: vec![
+            Cube { color: "blue".to_string(), quantity: 3 },
+            Cube { color: "red".to_string(), quantity: 4 },
+        ] })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_game() -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let result = parse_game(input);
+        assert_eq!(result, Ok(("", Game { number: 1, rounds: vec![
+            Round { cubes: vec![
+                Cube { color: "blue".to_string(), quantity: 3 },
+                Cube { color: "red".to_string(), quantity: 4 },
+            ] },
+            Round { cubes: vec![
+                Cube { color: "red".to_string(), quantity: 1 },
+                Cube { color: "green".to_string(), quantity: 2 },
+                Cube { color: "blue".to_string(), quantity: 6 },
+            ] },
+            Round { cubes: vec![
+                Cube { color: "green".to_string(), quantity: 2 },
+            ] },
+        ] })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_games() -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue";
+        let result = parse_games(input);
+        assert_eq!(result, Ok(("", vec![
+            Game {
+                number: 1,
+                rounds: vec![
+                    Round {
+                        cubes: vec![
+                            Cube { color: "blue".to_string(), quantity: 3 },
+                            Cube { color: "red".to_string(), quantity: 4 },
+                        ]
+                    },
+                    Round {
+                        cubes: vec![
+                            Cube { color: "red".to_string(), quantity: 1 },
+                            Cube { color: "green".to_string(), quantity: 2 },
+                            Cube { color: "blue".to_string(), quantity: 6 },
+                        ]
+                    },
+                    Round {
+                        cubes: vec![
+                            Cube { color: "green".to_string(), quantity: 2 },
+                        ]
+                    },
+                ]
+            },
+            Game {
+                number: 2,
+                rounds: vec![
+                    Round {
+                        cubes: vec![
+                            Cube { color: "blue".to_string(), quantity: 1 },
+                            Cube { color: "green".to_string(), quantity: 2 },
+                        ]
+                    },
+                    Round {
+                        cubes: vec![
+                            Cube { color: "green".to_string(), quantity: 3 },
+                            Cube { color: "blue".to_string(), quantity: 4 },
+                            Cube { color: "red".to_string(), quantity: 1 },
+                        ]
+                    },
+                    Round {
+                        cubes: vec![
+                            Cube { color: "green".to_string(), quantity: 1 },
+                            Cube { color: "blue".to_string(), quantity: 1 },
+                        ]
+                    },
+                ]
+            },
+        ])));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // too many cubes for a u32
+        assert!(parse_cube("99999999999 blue").is_err());
+        assert!(matches!(parse(""), Err(AocError::ParseError(_))));
+    }
+}
\ No newline at end of file
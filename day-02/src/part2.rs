@@ -1,30 +1,7 @@
 use std::collections::HashMap;
 use crate::custom_error::AocError;
-use nom::{IResult};
-use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, digit1, line_ending};
-use nom::multi::separated_list1;
-use nom::sequence::{preceded, separated_pair};
-
-#[derive(Debug)]
-#[derive(PartialEq)]
-struct Cube {
-    color: String,
-    quantity: u32,
-}
-
-#[derive(Debug)]
-#[derive(PartialEq)]
-struct Round {
-    cubes: Vec<Cube>,
-}
-
-#[derive(Debug)]
-#[derive(PartialEq)]
-struct Game {
-    number: u32,
-    rounds: Vec<Round>,
-}
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Game, Parsed};
 
 impl Game {
     fn max_cubes_per_color(&self) -> HashMap<String, u32> {
@@ -48,63 +25,82 @@ impl Game {
         }
         result
     }
-    fn set_power(&self) -> u32 {
-        self.max_cubes_per_color().values().product()
+    // `None` if it doesn't fit in a u64, three u32 counts can take 96 bits
+    fn set_power(&self) -> Option<u64> {
+        // a color that's never drawn needs no cubes at all, making the power 0
+        let max_cubes = self.max_cubes_per_color();
+        ["red", "green", "blue"]
+            .iter()
+            .map(|color| u64::from(max_cubes.get(*color).copied().unwrap_or(0)))
+            .try_fold(1u64, u64::checked_mul)
+    }
+    // Same as `set_power`, keeping the maximums in an array instead of a map
+    fn set_power_array(&self) -> Option<u64> {
+        let mut max_cubes = [0; 3];
+        for cube in self.rounds.iter().flat_map(|round| &round.cubes) {
+            let index = match cube.color.as_str() {
+                "red" => 0,
+                "green" => 1,
+                "blue" => 2,
+                _ => continue,
+            };
+            max_cubes[index] = max_cubes[index].max(cube.quantity);
+        }
+        max_cubes.iter().map(|&count| u64::from(count)).try_fold(1u64, u64::checked_mul)
     }
 }
 
-#[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    let (_, games) = parse_games(_input).expect("Failed to parse games");
-    let result = games.iter().map(|game| {
-        game.set_power()
-    });
-    Ok(result.sum::<u32>().to_string())
+// The sum of the games' powers, failing at the first game that takes it
+// past a u64
+fn sum_powers(games: &[Game], power: fn(&Game) -> Option<u64>) -> miette::Result<u64, AocError> {
+    games.iter().try_fold(0u64, |sum, game| {
+        power(game)
+            .and_then(|power| sum.checked_add(power))
+            .ok_or(AocError::PowerOverflow { game: game.number })
+    })
 }
 
-// input: 3 blue
-fn parse_cube(input: &str) -> IResult<&str, Cube> {
-    let (input, (quantity, color)) = separated_pair(
-        digit1, tag(" "), alpha1
-    )(input)?;
-    Ok((input, Cube { color: color.to_string(), quantity: quantity.parse::<u32>().unwrap() }))
+#[tracing::instrument(skip(games))]
+pub fn solve(
+    games: &[Game],
+) -> miette::Result<Answer, AocError> {
+    Ok(sum_powers(games, Game::set_power)?.into())
 }
 
-// input: 1 red, 2 green, 6 blue
-fn parse_round(input: &str) -> IResult<&str, Round> {
-    let (input, cubes) = separated_list1(tag(", "), parse_cube)(input)?;
-    Ok((input, Round { cubes }))
+#[tracing::instrument(name = "solve", skip(games))]
+pub fn solve_array(
+    games: &[Game],
+) -> miette::Result<Answer, AocError> {
+    Ok(sum_powers(games, Game::set_power_array)?.into())
 }
 
-// input: Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-fn parse_game(input: &str) -> IResult<&str, Game> {
-    let (input, (game_number, rounds)) = separated_pair(
-        preceded(tag("Game "), digit1), tag(": "), separated_list1(tag("; "), parse_round)
-    )(input)?;
-    Ok((input, Game { number: game_number.parse::<u32>().unwrap(), rounds }))
+#[tracing::instrument(skip(_input))]
+pub fn process(
+    _input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(_input)?)
 }
 
-fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
-    let (input, games) = separated_list1(line_ending, parse_game)(input)?;
-    Ok((input, games))
-}
+// Every way of solving this part, the first is the default
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "hashmap", solve: |parsed| Ok(solve(parsed)?) },
+    Variant { name: "array", solve: |parsed| Ok(solve_array(parsed)?) },
+];
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+    aoc_harness::example_tests!(process, "examples/part2/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part2/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part2/*.txt");
 
-        assert_eq!("2286", process(input)?);
+    #[test]
+    fn test_variants_agree_on_generated() -> miette::Result<()> {
+        for seed in 0..20 {
+            aoc_harness::assert_variants_agree(VARIANTS, &parse(&crate::generate::generate(50, seed))?)?;
+        }
         Ok(())
     }
 
@@ -114,8 +110,26 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
     #[case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", 1560)]
     #[case("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", 630)]
     #[case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", 36)]
-    fn test_game_set_power(#[case] input: &str, #[case] expected: u32) {
-        let (_, games) = parse_games(input).expect("Failed to parse games");
-        assert_eq!(games[0].set_power(), expected);
+    #[case("Game 6: 6 red, 1 blue; 2 blue, 1 red", 0)]
+    #[case("Game 7: 70000 red, 70000 green, 70000 blue", 343_000_000_000_000)]
+    fn test_game_set_power(#[case] input: &str, #[case] expected: u64) {
+        let games = parse(input).expect("Failed to parse games");
+        assert_eq!(games[0].set_power(), Some(expected));
+        assert_eq!(games[0].set_power_array(), Some(expected));
+    }
+
+    #[rstest]
+    // a single power past a u64
+    #[case("Game 1: 1 red\nGame 2: 4294967295 red, 4294967295 green, 4294967295 blue\n")]
+    // powers that fit, but not their sum
+    #[case("Game 1: 4294967295 red, 4294967295 green, 1 blue\nGame 2: 4294967295 red, 4294967295 green, 1 blue\n")]
+    fn test_power_overflow(#[case] input: &str) {
+        let games = parse(input).expect("Failed to parse games");
+        for variant in VARIANTS {
+            assert!(
+                matches!((variant.solve)(&games), Err(e) if e.to_string().contains("from game 2 on")),
+                "{variant}"
+            );
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,23 @@
+// The games repeated `factor` times, renumbered so every id stays unique
+pub fn scaled(input: &str, factor: usize) -> String {
+    let mut scaled = String::with_capacity((input.len() + 8) * factor);
+    let games = input.lines().filter_map(|line| line.split_once(": "));
+    for (id, (_, rounds)) in (1..).zip(games.cycle().take(factor * input.lines().count())) {
+        scaled.push_str(&format!("Game {id}: {rounds}\n"));
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 green\n";
+        assert_eq!(
+            "Game 1: 3 blue, 4 red\nGame 2: 1 green\nGame 3: 3 blue, 4 red\nGame 4: 1 green\n",
+            scaled(input, 2)
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use day_03::custom_error::AocError;
+use day_03::render;
+use day_03::schematic::Schematic;
+use miette::Context;
+
+// Print the coloured schematic, and also write it as an html page when a
+// path is given, e.g. `cargo run -p day-03 --bin render -- renders/day-03.html`
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let file = include_str!("../../input1.txt");
+    let schematic = Schematic::parse(file)
+        .context("parse schematic")?;
+    print!("{}", render::ansi(file, &schematic));
+
+    if let Some(path) = std::env::args().nth(1) {
+        let path = std::path::Path::new(&path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(AocError::from)?;
+        }
+        std::fs::write(
+            path,
+            render::html(file, &schematic),
+        )
+        .map_err(AocError::from)
+        .with_context(|| {
+            format!("write {}", path.display())
+        })?;
+    }
+    Ok(())
+}
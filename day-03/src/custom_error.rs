@@ -7,4 +7,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
+
+    #[error("number starting at line {line}, column {column} does not fit in a u32")]
+    #[diagnostic(code(aoc::number_too_large))]
+    NumberTooLarge { line: usize, column: usize },
 }
\ No newline at end of file
@@ -0,0 +1,62 @@
+use fastrand::Rng;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '%', '=', '&', '-'];
+
+// A schematic of `size` rows as wide as the real one, sprinkled with numbers
+// of up to three digits and symbols, gears included
+pub fn generate(size: usize, seed: u64) -> String {
+    const WIDTH: usize = 140;
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::with_capacity((WIDTH + 1) * size);
+    for _ in 0..size {
+        let mut x = 0;
+        while x < WIDTH {
+            match rng.u8(0..20) {
+                0..=2 => {
+                    // numbers are kept apart by at least one other cell
+                    let digits = rng.usize(1..=3).min(WIDTH - x);
+                    input.push(rng.char('1'..='9'));
+                    for _ in 1..digits {
+                        input.push(rng.char('0'..='9'));
+                    }
+                    x += digits;
+                    if x < WIDTH {
+                        input.push('.');
+                        x += 1;
+                    }
+                    continue;
+                }
+                3 => input.push(SYMBOLS[rng.usize(0..SYMBOLS.len())]),
+                _ => input.push('.'),
+            }
+            x += 1;
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::Schematic;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated_input_solves() -> miette::Result<()> {
+        let input = generate(100, 1);
+        assert!(input.lines().all(|line| line.len() == 140));
+        let schematic = Schematic::parse(&input)?;
+        assert!(schematic.part_numbers().count() > 0);
+        assert!(schematic.gears().count() > 0);
+        part1::process(&input)?;
+        part2::process(&input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
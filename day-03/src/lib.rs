@@ -1,4 +1,16 @@
 pub mod custom_error;
 
+pub mod generate;
+pub mod parsed;
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod render;
+pub mod scale;
+pub mod schematic;
+
+use aoc_harness::Puzzle;
+
+pub const PUZZLE: Puzzle<parsed::Parsed> = Puzzle {
+    parse: |input| Ok(parsed::parse(input)?),
+    parts: [part1::VARIANTS, part2::VARIANTS],
+};
\ No newline at end of file
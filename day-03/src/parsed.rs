@@ -0,0 +1,13 @@
+use crate::custom_error::AocError;
+use crate::schematic::Schematic;
+
+// Both parts look for numbers around the symbols of the same schematic
+pub type Parsed = Schematic;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    Schematic::parse(&input)
+}
\ No newline at end of file
@@ -1,159 +1,50 @@
 use crate::custom_error::AocError;
-use itertools::Itertools;
-use std::collections::BTreeMap;
-
-#[derive(Debug)]
-enum Value {
-    Symbol(char),
-    Empty,
-    Number(u32),
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
+use crate::schematic::Schematic;
+
+#[tracing::instrument(skip(schematic))]
+pub fn solve(
+    schematic: &Schematic,
+) -> miette::Result<Answer, AocError> {
+    // sum gear ratios
+    let total = schematic
+        .gears()
+        .map(|(_, [a, b])| a.value as u64 * b.value as u64)
+        .sum::<u64>();
+
+    Ok(total.into())
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input))]
 pub fn process(
     input: &str,
-) -> miette::Result<String, AocError> {
-    // sum part numbers
-    let map = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(
-                move |(x, character)| {
-                    (
-                (y as i32,x as i32),
-                match character {
-                    '.' => Value::Empty,
-                    c if c.is_ascii_digit() => {
-                        Value::Number(
-                            c.to_digit(10).expect(
-                                "should be a number",
-                            ),
-                        )
-                    }
-                    c => Value::Symbol(c),
-                },
-            )
-                },
-            )
-        })
-        .collect::<BTreeMap<(i32, i32), Value>>();
-
-    let mut numbers: Vec<Vec<((i32, i32), u32)>> = vec![];
-    for ((y, x), value) in map.iter() {
-        if let Value::Number(num) = value {
-            match numbers.iter().last() {
-                Some(v) => {
-                    let last_num = v.iter().last();
-                    match last_num {
-                        Some(((last_num_x, _), _)) => {
-                            if last_num_x + 1 == *x {
-                                let last = numbers
-                                    .iter_mut()
-                                    .last()
-                                    .expect("should exist");
-                                last.push(((*x, *y), *num));
-                            } else {
-                                numbers.push(vec![(
-                                    (*x, *y),
-                                    *num,
-                                )]);
-                            }
-                        }
-                        None => unimplemented!(
-                            "shouldn't happen"
-                        ),
-                    }
-                }
-                None => {
-                    numbers.push(vec![((*x, *y), *num)]);
-                }
-            }
-        }
-    }
-
-    // map: entire grid
-    // numbers: sequential numbers
-    let mut total = 0;
-    for symbol in map.iter().filter(|(_key, value)| {
-        matches!(value, Value::Symbol('*'))
-    }) {
-        // (x,y)
-        let positions = [
-            (1, 0),
-            (1, -1),
-            (0, -1),
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, 1),
-            (1, 1),
-        ];
-        let pos_to_check: Vec<(i32, i32)> = positions
-            .iter()
-            .map(|outer_pos| {
-                // outer_pos.x + pos.x, .y + .y
-                (
-                    outer_pos.0 + symbol.0 .1,
-                    outer_pos.1 + symbol.0 .0,
-                )
-            })
-            .collect();
-
-        // dbg!(pos_to_check.len(), pos_to_check);
-        let mut indexes_of_numbers = vec![];
-
-        for pos in pos_to_check {
-            for (i, num_list) in numbers.iter().enumerate()
-            {
-                if num_list
-                    .iter()
-                    .any(|(num_pos, _)| num_pos == &pos)
-                {
-                    indexes_of_numbers.push(i);
-                }
-            }
-        }
-
-        let is_gear =
-            indexes_of_numbers.iter().unique().count() == 2;
-
-        if is_gear {
-            total += indexes_of_numbers
-                .iter()
-                .unique()
-                .map(|index| {
-                    numbers[*index]
-                        .iter()
-                        .map(|(_, num)| num.to_string())
-                        .collect::<String>()
-                        .parse::<usize>()
-                        .unwrap()
-                })
-                .product::<usize>();
-        }
-    }
-
-    Ok(total.to_string())
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(input)?)
 }
 
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        assert_eq!("467835", process(input)?);
+    use rstest::rstest;
+
+    aoc_harness::example_tests!(process, "examples/part2/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part2/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part2/*.txt");
+
+    #[rstest]
+    #[case("1*\n.2", "2")]
+    #[case("12*\n..3\n4..", "36")]
+    #[case("..5\n*6.", "0")]
+    fn test_numbers_across_lines(
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,206 @@
+use std::fmt::Write;
+
+use crate::schematic::{Position, Schematic};
+
+// What a single cell of the schematic is drawn as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    PartNumber(char),
+    OtherNumber(char),
+    Gear,
+    Symbol(char),
+}
+
+impl Cell {
+    fn character(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Gear => '*',
+            Cell::PartNumber(c)
+            | Cell::OtherNumber(c)
+            | Cell::Symbol(c) => c,
+        }
+    }
+
+    fn ansi_style(self) -> &'static str {
+        match self {
+            Cell::Empty => "\x1b[2m",
+            Cell::PartNumber(_) => "\x1b[1;32m",
+            Cell::OtherNumber(_) => "\x1b[31m",
+            Cell::Gear => "\x1b[1;33m",
+            Cell::Symbol(_) => "\x1b[36m",
+        }
+    }
+
+    fn html_class(self) -> &'static str {
+        match self {
+            Cell::Empty => "empty",
+            Cell::PartNumber(_) => "part",
+            Cell::OtherNumber(_) => "other",
+            Cell::Gear => "gear",
+            Cell::Symbol(_) => "symbol",
+        }
+    }
+}
+
+// Classify every cell of the input, line by line
+pub fn cells(
+    input: &str,
+    schematic: &Schematic,
+) -> Vec<Vec<Cell>> {
+    let gears: Vec<Position> =
+        schematic.gears().map(|(pos, _)| pos).collect();
+
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    let pos =
+                        Position::new(x as i32, y as i32);
+                    match schematic.number_at(pos) {
+                        Some(number)
+                            if schematic
+                                .is_part_number(number) =>
+                        {
+                            Cell::PartNumber(c)
+                        }
+                        Some(_) => Cell::OtherNumber(c),
+                        None if gears.contains(&pos) => {
+                            Cell::Gear
+                        }
+                        None if c == '.' => Cell::Empty,
+                        None => Cell::Symbol(c),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Colour the schematic for a terminal, consecutive cells of the same kind
+// share a single escape sequence
+pub fn ansi(input: &str, schematic: &Schematic) -> String {
+    let mut output = String::new();
+    for line in cells(input, schematic) {
+        let mut previous = None;
+        for cell in line {
+            let style = cell.ansi_style();
+            if previous != Some(style) {
+                output.push_str("\x1b[0m");
+                output.push_str(style);
+                previous = Some(style);
+            }
+            output.push(cell.character());
+        }
+        output.push_str("\x1b[0m\n");
+    }
+    output
+}
+
+// A standalone page with the same colours as the terminal view
+pub fn html(input: &str, schematic: &Schematic) -> String {
+    let mut output = String::from(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>day-03 engine schematic</title>
+<style>
+body { background: #111; color: #ccc; }
+pre { font-family: monospace; line-height: 1.1; }
+.empty { color: #444; }
+.part { color: #4e4; font-weight: bold; }
+.other { color: #e44; }
+.gear { color: #ee4; font-weight: bold; background: #442; }
+.symbol { color: #4ee; }
+</style>
+</head>
+<body>
+<pre>",
+    );
+    for line in cells(input, schematic) {
+        for cell in line {
+            let character = match cell.character() {
+                '&' => "&amp;".to_string(),
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                c => c.to_string(),
+            };
+            write!(
+                output,
+                "<span class=\"{}\">{}</span>",
+                cell.html_class(),
+                character
+            )
+            .expect("writing to a String can't fail");
+        }
+        output.push('\n');
+    }
+    output.push_str("</pre>\n</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_cells() {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        let grid = cells(INPUT, &schematic);
+        assert_eq!(Cell::PartNumber('4'), grid[0][0]);
+        assert_eq!(Cell::OtherNumber('1'), grid[0][5]);
+        assert_eq!(Cell::Gear, grid[1][3]);
+        // a `*` next to a single number is not a gear
+        assert_eq!(Cell::Symbol('*'), grid[4][3]);
+        assert_eq!(Cell::Symbol('#'), grid[3][6]);
+        assert_eq!(Cell::Empty, grid[9][0]);
+    }
+
+    #[test]
+    fn test_ansi_keeps_text() {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        let rendered = ansi(INPUT, &schematic);
+        let mut plain = String::new();
+        let mut chars = rendered.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| *c == 'm');
+            } else {
+                plain.push(c);
+            }
+        }
+        assert_eq!(format!("{INPUT}\n"), plain);
+    }
+
+    #[test]
+    fn test_html_escapes_symbols() {
+        let input = "1&.\n..<";
+        let schematic = Schematic::parse(input).unwrap();
+        let page = html(input, &schematic);
+        assert!(page.contains(
+            "<span class=\"symbol\">&amp;</span>"
+        ));
+        assert!(page.contains(
+            "<span class=\"symbol\">&lt;</span>"
+        ));
+        assert!(
+            page.contains("<span class=\"part\">1</span>")
+        );
+    }
+}
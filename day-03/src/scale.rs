@@ -0,0 +1,23 @@
+// The schematic stacked `factor` times, the rows all keep the same width
+pub fn scaled(input: &str, factor: usize) -> String {
+    let mut scaled = String::with_capacity((input.len() + 1) * factor);
+    for _ in 0..factor {
+        for line in input.lines() {
+            scaled.push_str(line);
+            scaled.push('\n');
+        }
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled() -> miette::Result<()> {
+        let schematic = crate::schematic::Schematic::parse(&scaled("467..\n...*.", 3))?;
+        assert_eq!(3, schematic.part_numbers().count());
+        Ok(())
+    }
+}
\ No newline at end of file
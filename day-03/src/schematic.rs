@@ -0,0 +1,473 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::custom_error::AocError;
+
+// A cell in the schematic, x is the column and y is the line
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    // The eight cells around this one, some of them may be off the grid
+    pub fn neighbours(
+        self,
+    ) -> impl Iterator<Item = Position> {
+        const OFFSETS: [(i32, i32); 8] = [
+            (1, 0),
+            (1, -1),
+            (0, -1),
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+        OFFSETS.iter().map(move |(dx, dy)| {
+            Position::new(self.x + dx, self.y + dy)
+        })
+    }
+}
+
+// A run of digits on a single line, it never wraps onto the next line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub start: Position,
+    pub len: i32,
+}
+
+impl Number {
+    pub fn positions(
+        &self,
+    ) -> impl Iterator<Item = Position> + '_ {
+        (0..self.len).map(|dx| {
+            Position::new(self.start.x + dx, self.start.y)
+        })
+    }
+
+    // Every cell touching the number, including diagonals, but not the number itself
+    pub fn border(
+        &self,
+    ) -> impl Iterator<Item = Position> + '_ {
+        let Position { x, y } = self.start;
+        (x - 1..=x + self.len)
+            .flat_map(move |bx| {
+                [y - 1, y + 1]
+                    .into_iter()
+                    .map(move |by| Position::new(bx, by))
+            })
+            .chain([
+                Position::new(x - 1, y),
+                Position::new(x + self.len, y),
+            ])
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: BTreeMap<Position, char>,
+    // Which number (index into `numbers`) covers a given digit cell
+    digits: HashMap<Position, usize>,
+    width: i32,
+    height: i32,
+}
+
+impl Schematic {
+    #[tracing::instrument(skip(input))]
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut numbers: Vec<Number> = vec![];
+        let mut symbols = BTreeMap::new();
+        let mut digits = HashMap::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let y = y as i32;
+            height = y + 1;
+            // A number being read on this line, it is closed at the first non-digit
+            // or at the end of the line.
+            let mut current: Option<Number> = None;
+            for (x, character) in line.chars().enumerate() {
+                let x = x as i32;
+                width = width.max(x + 1);
+                match character.to_digit(10) {
+                    Some(digit) => {
+                        let number =
+                            current.get_or_insert(Number {
+                                value: 0,
+                                start: Position::new(x, y),
+                                len: 0,
+                            });
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| {
+                                value.checked_add(digit)
+                            })
+                            .ok_or(
+                                AocError::NumberTooLarge {
+                                    line: y as usize + 1,
+                                    column: number.start.x
+                                        as usize
+                                        + 1,
+                                },
+                            )?;
+                        number.len += 1;
+                        digits.insert(
+                            Position::new(x, y),
+                            numbers.len(),
+                        );
+                    }
+                    None => {
+                        numbers.extend(current.take());
+                        if character != '.' {
+                            symbols.insert(
+                                Position::new(x, y),
+                                character,
+                            );
+                        }
+                    }
+                }
+            }
+            numbers.extend(current.take());
+        }
+
+        Ok(Self {
+            numbers,
+            symbols,
+            digits,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn number_at(
+        &self,
+        position: Position,
+    ) -> Option<&Number> {
+        self.digits
+            .get(&position)
+            .map(|i| &self.numbers[*i])
+    }
+
+    pub fn symbol_at(
+        &self,
+        position: Position,
+    ) -> Option<char> {
+        self.symbols.get(&position).copied()
+    }
+
+    pub fn symbols(
+        &self,
+    ) -> impl Iterator<Item = (Position, char)> + '_ {
+        self.symbols.iter().map(|(pos, c)| (*pos, *c))
+    }
+
+    pub fn is_part_number(&self, number: &Number) -> bool {
+        number
+            .border()
+            .any(|pos| self.symbols.contains_key(&pos))
+    }
+
+    pub fn part_numbers(
+        &self,
+    ) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .filter(|number| self.is_part_number(number))
+    }
+
+    // The distinct numbers touching a cell, in reading order
+    pub fn adjacent_numbers(
+        &self,
+        position: Position,
+    ) -> Vec<&Number> {
+        let mut indexes: Vec<usize> = position
+            .neighbours()
+            .filter_map(|pos| {
+                self.digits.get(&pos).copied()
+            })
+            .collect();
+        indexes.sort_unstable();
+        indexes.dedup();
+        indexes.iter().map(|i| &self.numbers[*i]).collect()
+    }
+
+    // A gear is a `*` touching exactly two numbers
+    pub fn gears(
+        &self,
+    ) -> impl Iterator<Item = (Position, [&Number; 2])>
+    {
+        self.symbols
+            .iter()
+            .filter(|(_, c)| **c == '*')
+            .filter_map(|(pos, _)| {
+                match self.adjacent_numbers(*pos)[..] {
+                    [a, b] => Some((*pos, [a, b])),
+                    _ => None,
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    // Straightforward grid scan, used as the oracle for the property tests
+    mod reference {
+        fn grid(input: &str) -> Vec<Vec<char>> {
+            input
+                .lines()
+                .map(|line| line.chars().collect())
+                .collect()
+        }
+
+        fn is_symbol(c: char) -> bool {
+            c != '.' && !c.is_ascii_digit()
+        }
+
+        // (line, first column, last column, value)
+        fn numbers(
+            grid: &[Vec<char>],
+        ) -> Vec<(usize, usize, usize, u64)> {
+            let mut result = vec![];
+            for (y, row) in grid.iter().enumerate() {
+                let mut x = 0;
+                while x < row.len() {
+                    if row[x].is_ascii_digit() {
+                        let start = x;
+                        while x < row.len()
+                            && row[x].is_ascii_digit()
+                        {
+                            x += 1;
+                        }
+                        let value = row[start..x]
+                            .iter()
+                            .collect::<String>()
+                            .parse()
+                            .unwrap();
+                        result.push((
+                            y,
+                            start,
+                            x - 1,
+                            value,
+                        ));
+                    } else {
+                        x += 1;
+                    }
+                }
+            }
+            result
+        }
+
+        fn touches(
+            (y, first, last, _): (usize, usize, usize, u64),
+            (cy, cx): (usize, usize),
+        ) -> bool {
+            cy + 1 >= y
+                && cy <= y + 1
+                && cx + 1 >= first
+                && cx <= last + 1
+        }
+
+        pub fn part1(input: &str) -> u64 {
+            let grid = grid(input);
+            numbers(&grid)
+                .into_iter()
+                .filter(|&number| {
+                    grid.iter().enumerate().any(
+                        |(cy, row)| {
+                            row.iter().enumerate().any(
+                                |(cx, c)| {
+                                    is_symbol(*c)
+                                        && touches(
+                                            number,
+                                            (cy, cx),
+                                        )
+                                },
+                            )
+                        },
+                    )
+                })
+                .map(|(_, _, _, value)| value)
+                .sum()
+        }
+
+        pub fn part2(input: &str) -> u64 {
+            let grid = grid(input);
+            let numbers = numbers(&grid);
+            let mut total = 0;
+            for (cy, row) in grid.iter().enumerate() {
+                for (cx, c) in row.iter().enumerate() {
+                    if *c != '*' {
+                        continue;
+                    }
+                    let adjacent: Vec<u64> = numbers
+                        .iter()
+                        .filter(|&&number| {
+                            touches(number, (cy, cx))
+                        })
+                        .map(|(_, _, _, value)| *value)
+                        .collect();
+                    if adjacent.len() == 2 {
+                        total += adjacent[0] * adjacent[1];
+                    }
+                }
+            }
+            total
+        }
+    }
+
+    // Random rectangular schematics, narrow enough that numbers and gear
+    // products can't overflow
+    fn schematic_strategy() -> impl Strategy<Value = String>
+    {
+        let cell = prop_oneof![
+            6 => Just('.'),
+            4 => proptest::char::range('0', '9'),
+            1 => prop::sample::select(vec!['*', '#', '+', '$', '/', '=', '@', '%', '&', '-']),
+        ];
+        (1usize..=8, 1usize..=8).prop_flat_map(
+            move |(width, height)| {
+                prop::collection::vec(
+                    prop::collection::vec(
+                        cell.clone(),
+                        width,
+                    ),
+                    height,
+                )
+                .prop_map(|rows| {
+                    rows.into_iter()
+                        .map(|row| {
+                            row.into_iter()
+                                .collect::<String>()
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn part1_matches_reference(input in schematic_strategy()) {
+            let schematic = Schematic::parse(&input).unwrap();
+            let total: u64 = schematic.part_numbers().map(|n| n.value as u64).sum();
+            prop_assert_eq!(reference::part1(&input), total);
+        }
+
+        #[test]
+        fn part2_matches_reference(input in schematic_strategy()) {
+            let schematic = Schematic::parse(&input).unwrap();
+            let total: u64 = schematic
+                .gears()
+                .map(|(_, [a, b])| a.value as u64 * b.value as u64)
+                .sum();
+            prop_assert_eq!(reference::part2(&input), total);
+        }
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        let schematic =
+            Schematic::parse("467..114..\n...*......")
+                .unwrap();
+        assert_eq!(
+            schematic.numbers(),
+            &[
+                Number {
+                    value: 467,
+                    start: Position::new(0, 0),
+                    len: 3
+                },
+                Number {
+                    value: 114,
+                    start: Position::new(5, 0),
+                    len: 3
+                },
+            ]
+        );
+        assert_eq!(
+            Some('*'),
+            schematic.symbol_at(Position::new(3, 1))
+        );
+        assert_eq!(
+            (10, 2),
+            (schematic.width(), schematic.height())
+        );
+    }
+
+    #[rstest]
+    // a number ending a line and one starting the next are separate numbers
+    #[case("..12\n34..", &[12, 34])]
+    #[case("9\n9", &[9, 9])]
+    #[case("1.2", &[1, 2])]
+    fn test_numbers_do_not_wrap(
+        #[case] input: &str,
+        #[case] expected: &[u32],
+    ) {
+        let schematic = Schematic::parse(input).unwrap();
+        let values: Vec<u32> = schematic
+            .numbers()
+            .iter()
+            .map(|n| n.value)
+            .collect();
+        assert_eq!(expected, values);
+    }
+
+    #[rstest]
+    // the symbol at the start of the next line is not next to the number
+    #[case("..12\n*...", 0)]
+    #[case("..12\n.*..", 12)]
+    #[case("12.\n..#", 12)]
+    #[case("12..\n...#", 0)]
+    fn test_part_numbers_at_line_edges(
+        #[case] input: &str,
+        #[case] expected: u32,
+    ) {
+        let schematic = Schematic::parse(input).unwrap();
+        assert_eq!(
+            expected,
+            schematic
+                .part_numbers()
+                .map(|n| n.value)
+                .sum::<u32>()
+        );
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        assert!(matches!(
+            Schematic::parse("..99999999999"),
+            Err(AocError::NumberTooLarge {
+                line: 1,
+                column: 3
+            })
+        ));
+    }
+}
@@ -1,27 +1,69 @@
 use criterion::{
-    criterion_group, criterion_main, Criterion,
+    BenchmarkId, criterion_group, criterion_main, Criterion, Throughput,
 };
 use day_04::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
     let input = include_str!("../input1.txt");
+    let parsed = parsed::parse(input).unwrap();
 
     let mut group = c.benchmark_group("day_04::part1");
-    group.bench_with_input("part1", input, |b, input| {
+    group.bench_with_input("parse", input, |b, input| {
+        b.iter(|| parsed::parse(input))
+    });
+    group.bench_with_input("solve", &parsed, |b, parsed| {
+        b.iter(|| part1::solve(parsed))
+    });
+    group.bench_with_input("process", input, |b, input| {
         b.iter(|| part1::process(input))
     });
 
+    for variant in part1::VARIANTS {
+        group.bench_with_input(BenchmarkId::new("variant", variant.name), &parsed, |b, parsed| {
+            b.iter(|| (variant.solve)(parsed))
+        });
+    }
+
+    for factor in [1, 10, 100] {
+        let scaled = scale::scaled(input, factor);
+        group.throughput(Throughput::Bytes(scaled.len() as u64));
+        group.bench_with_input(BenchmarkId::new("scaled", factor), &scaled, |b, input| {
+            b.iter(|| part1::process(input))
+        });
+    }
+
     group.finish();
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
     let input = include_str!("../input2.txt");
+    let parsed = parsed::parse(input).unwrap();
 
     let mut group = c.benchmark_group("day_04::part2");
-    group.bench_with_input("part2", input, |b, input| {
+    group.bench_with_input("parse", input, |b, input| {
+        b.iter(|| parsed::parse(input))
+    });
+    group.bench_with_input("solve", &parsed, |b, parsed| {
+        b.iter(|| part2::solve(parsed))
+    });
+    group.bench_with_input("process", input, |b, input| {
         b.iter(|| part2::process(input))
     });
 
+    for variant in part2::VARIANTS {
+        group.bench_with_input(BenchmarkId::new("variant", variant.name), &parsed, |b, parsed| {
+            b.iter(|| (variant.solve)(parsed))
+        });
+    }
+
+    for factor in [1, 10, 100] {
+        let scaled = scale::scaled(input, factor);
+        group.throughput(Throughput::Bytes(scaled.len() as u64));
+        group.bench_with_input(BenchmarkId::new("scaled", factor), &scaled, |b, input| {
+            b.iter(|| part2::process(input))
+        });
+    }
+
     group.finish();
 }
 
@@ -1,22 +1,111 @@
-use day_04::*;
-
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-        .unwrap();
+// Every part is timed as parse, solve and the two combined, `variants`
+// solves the parsed input with each registered variant, `scaled` runs the
+// whole part on the input repeated 1, 10 and 100 times and `generated` on
+// synthetic inputs of growing size
+
+#[divan::bench_group]
+mod part1 {
+    use day_04::parsed::{self, Parsed};
+    use day_04::part1;
+    use aoc_harness::Variant;
+
+    const INPUT: &str = include_str!("../input1.txt");
+
+    #[divan::bench]
+    fn parse() {
+        parsed::parse(divan::black_box(INPUT)).unwrap();
+    }
+
+    #[divan::bench]
+    fn solve(bencher: divan::Bencher) {
+        let parsed = parsed::parse(INPUT).unwrap();
+        bencher.bench(|| {
+            part1::solve(divan::black_box(&parsed)).unwrap()
+        });
+    }
+
+    #[divan::bench]
+    fn process() {
+        part1::process(divan::black_box(INPUT)).unwrap();
+    }
+
+    #[divan::bench(args = part1::VARIANTS)]
+    fn variants(bencher: divan::Bencher, variant: &Variant<Parsed>) {
+        let parsed = parsed::parse(INPUT).unwrap();
+        bencher.bench(|| {
+            (variant.solve)(divan::black_box(&parsed)).unwrap()
+        });
+    }
+
+    #[divan::bench(args = [1, 10, 100])]
+    fn scaled(bencher: divan::Bencher, factor: usize) {
+        let input = day_04::scale::scaled(INPUT, factor);
+        bencher.bench(|| {
+            part1::process(divan::black_box(&input)).unwrap()
+        });
+    }
+
+    #[divan::bench(args = [100, 1000, 10000])]
+    fn generated(bencher: divan::Bencher, size: usize) {
+        let input = day_04::generate::generate(size, 2023);
+        bencher.bench(|| {
+            part1::process(divan::black_box(&input)).unwrap()
+        });
+    }
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-        .unwrap();
+#[divan::bench_group]
+mod part2 {
+    use day_04::parsed::{self, Parsed};
+    use day_04::part2;
+    use aoc_harness::Variant;
+
+    const INPUT: &str = include_str!("../input2.txt");
+
+    #[divan::bench]
+    fn parse() {
+        parsed::parse(divan::black_box(INPUT)).unwrap();
+    }
+
+    #[divan::bench]
+    fn solve(bencher: divan::Bencher) {
+        let parsed = parsed::parse(INPUT).unwrap();
+        bencher.bench(|| {
+            part2::solve(divan::black_box(&parsed)).unwrap()
+        });
+    }
+
+    #[divan::bench]
+    fn process() {
+        part2::process(divan::black_box(INPUT)).unwrap();
+    }
+
+    #[divan::bench(args = part2::VARIANTS)]
+    fn variants(bencher: divan::Bencher, variant: &Variant<Parsed>) {
+        let parsed = parsed::parse(INPUT).unwrap();
+        bencher.bench(|| {
+            (variant.solve)(divan::black_box(&parsed)).unwrap()
+        });
+    }
+
+    #[divan::bench(args = [1, 10, 100])]
+    fn scaled(bencher: divan::Bencher, factor: usize) {
+        let input = day_04::scale::scaled(INPUT, factor);
+        bencher.bench(|| {
+            part2::process(divan::black_box(&input)).unwrap()
+        });
+    }
+
+    #[divan::bench(args = [100, 1000, 10000])]
+    fn generated(bencher: divan::Bencher, size: usize) {
+        let input = day_04::generate::generate(size, 2023);
+        bencher.bench(|| {
+            part2::process(divan::black_box(&input)).unwrap()
+        });
+    }
 }
\ No newline at end of file
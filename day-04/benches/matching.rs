@@ -0,0 +1,60 @@
+use day_04::card::{number_set, scan_matches, Card};
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+// (winning, mine) for every card of the real input
+fn number_lists() -> Vec<(Vec<u32>, Vec<u32>)> {
+    include_str!("../input1.txt")
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(':').unwrap();
+            let (winning, mine) = numbers.split_once('|').unwrap();
+            let parse = |list: &str| {
+                list.split_whitespace()
+                    .map(|n| n.parse().unwrap())
+                    .collect()
+            };
+            (parse(winning), parse(mine))
+        })
+        .collect()
+}
+
+#[divan::bench]
+fn vec_scan(bencher: divan::Bencher) {
+    let lists = number_lists();
+    bencher.bench(|| {
+        divan::black_box(&lists)
+            .iter()
+            .map(|(winning, mine)| scan_matches(winning, mine))
+            .sum::<usize>()
+    });
+}
+
+#[divan::bench]
+fn bitset_popcount(bencher: divan::Bencher) {
+    let cards: Vec<Card> = number_lists()
+        .iter()
+        .enumerate()
+        .map(|(i, (winning, mine))| Card::new(i as u32 + 1, winning, mine).unwrap())
+        .collect();
+    bencher.bench(|| {
+        divan::black_box(&cards)
+            .iter()
+            .map(|card| card.matches() as usize)
+            .sum::<usize>()
+    });
+}
+
+#[divan::bench]
+fn bitset_build(bencher: divan::Bencher) {
+    let lists = number_lists();
+    bencher.bench(|| {
+        divan::black_box(&lists)
+            .iter()
+            .map(|(winning, mine)| (number_set(winning).unwrap() & number_set(mine).unwrap()).count_ones())
+            .sum::<u32>()
+    });
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use day_04::card::parse_cards;
+use day_04::cascade::{Cascade, Mode};
+use miette::Context;
+
+// Print how many copies of each card we end up with,
+// `cargo run -p day-04 --bin cascade -- --strict` fails on wins past the last card
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let mode = if std::env::args().any(|arg| arg == "--strict") {
+        Mode::Strict
+    } else {
+        Mode::Lenient
+    };
+    let file = include_str!("../../input2.txt");
+    let (_, cards) = parse_cards(file).expect("Failed to parse cards");
+    let cascade = Cascade::new(&cards, mode).context("copy cards")?;
+    print!("{}", cascade);
+    println!("total: {}", cascade.total()?);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,143 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, line_ending, multispace0, multispace1};
+use nom::combinator::{map_res, verify};
+use nom::IResult;
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, preceded, separated_pair};
+
+use crate::custom_error::AocError;
+
+// Every number on the cards is below 100, so a set of them fits in a u128
+pub const MAX_NUMBER: u32 = u128::BITS - 1;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Card {
+    pub number: u32,
+    winning: u128,
+    mine: u128,
+}
+impl Card {
+    // Fails for numbers past `MAX_NUMBER`, they don't fit in the sets
+    pub fn new(number: u32, winning: &[u32], mine: &[u32]) -> Result<Self, AocError> {
+        let set = |numbers: &[u32]| {
+            number_set(numbers).ok_or_else(|| AocError::NumberTooLarge {
+                card: number,
+                number: numbers.iter().copied().max().unwrap_or_default(),
+            })
+        };
+        Ok(Self { number, winning: set(winning)?, mine: set(mine)? })
+    }
+    // How many of my numbers are winning numbers
+    pub fn matches(&self) -> u32 {
+        (self.winning & self.mine).count_ones()
+    }
+}
+
+// `None` if a number is past `MAX_NUMBER`
+pub fn number_set(numbers: &[u32]) -> Option<u128> {
+    numbers.iter().try_fold(0u128, |set, &n| Some(set | 1u128.checked_shl(n)?))
+}
+
+// The previous `Vec` based matching, only kept around to benchmark against
+pub fn scan_matches(winning: &[u32], mine: &[u32]) -> usize {
+    mine.iter().filter(|&i| winning.contains(i)).count()
+}
+
+pub fn parse_number_list(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(
+        multispace1,
+        verify(map_res(digit1, |d: &str| d.parse::<u32>()), |n| *n <= MAX_NUMBER),
+    )(input)
+}
+
+// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+pub fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, (number, (winning, mine))) = separated_pair(
+        preceded(tag("Card"), preceded(multispace1, map_res(digit1, |d: &str| d.parse::<u32>()))),
+        delimited(multispace0, tag(":"), multispace0),
+        separated_pair(parse_number_list, delimited(multispace0, tag("|"), multispace0), parse_number_list)
+    )(input)?;
+    // the number lists are already checked against `MAX_NUMBER`
+    let card = Card::new(number, &winning, &mine)
+        .map_err(|_| nom::Err::Failure(nom::error::Error::new(input, nom::error::ErrorKind::Verify)))?;
+    Ok((input, card))
+}
+
+pub fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
+    separated_list1(line_ending, parse_card)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse_cards() -> miette::Result<()> {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let result = parse_cards(input).expect("Failed to parse cards");
+        assert_eq!(result, ("", vec![
+            Card::new(1, &[41, 48, 83, 86, 17], &[83, 86, 6, 31, 17, 9, 48, 53]).unwrap(),
+            Card::new(2, &[13, 32, 20, 16, 61], &[61, 30, 68, 82, 17, 32, 24, 19]).unwrap(),
+            Card::new(3, &[1, 21, 53, 59, 44], &[69, 82, 63, 72, 16, 21, 14, 1]).unwrap(),
+            Card::new(4, &[41, 92, 73, 84, 69], &[59, 84, 76, 51, 58, 5, 54, 83]).unwrap(),
+            Card::new(5, &[87, 83, 26, 28, 32], &[88, 30, 70, 12, 93, 22, 82, 36]).unwrap(),
+            Card::new(6, &[31, 18, 13, 56, 72], &[74, 77, 10, 23, 35, 67, 36, 11]).unwrap(),
+        ]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_number_list() -> miette::Result<()> {
+        let input = "41 48 83 86 17";
+        let (_, numbers) = parse_number_list(input).unwrap();
+        assert_eq!(vec![41, 48, 83, 86, 17], numbers);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_number_list_out_of_range() {
+        assert!(parse_number_list("128").is_err());
+        assert!(parse_number_list("99999999999999999999").is_err());
+        assert_eq!(Ok(("", vec![127])), parse_number_list("127"));
+    }
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", Card::new(1, &[41, 48, 83, 86, 17], &[83, 86, 6, 31, 17, 9, 48, 53]).unwrap(), 4)]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", Card::new(2, &[13, 32, 20, 16, 61], &[61, 30, 68, 82, 17, 32, 24, 19]).unwrap(), 2)]
+    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", Card::new(3, &[1, 21, 53, 59, 44], &[69, 82, 63, 72, 16, 21, 14, 1]).unwrap(), 2)]
+    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", Card::new(5, &[87, 83, 26, 28, 32], &[88, 30, 70, 12, 93, 22, 82, 36]).unwrap(), 0)]
+    fn test_parse_card(#[case] input: &str, #[case] expected: Card, #[case] matches: u32) -> miette::Result<()> {
+        let (_, card) = parse_card(input).unwrap();
+        assert_eq!(expected, card);
+        assert_eq!(matches, card.matches());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(&[41, 48, 83, 86, 17], &[83, 86, 6, 31, 17, 9, 48, 53])]
+    #[case(&[0, 127, 64, 63], &[127, 63, 1])]
+    #[case(&[5], &[6])]
+    fn test_bitset_matches_scan(#[case] winning: &[u32], #[case] mine: &[u32]) {
+        let card = Card::new(1, winning, mine).unwrap();
+        assert_eq!(scan_matches(winning, mine), card.matches() as usize);
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        assert!(matches!(
+            Card::new(7, &[1, 128], &[2]),
+            Err(AocError::NumberTooLarge { card: 7, number: 128 })
+        ));
+        assert!(Card::new(7, &[1], &[u32::MAX]).is_err());
+        assert_eq!(None, number_set(&[200]));
+        assert_eq!(Some(1 << 127), number_set(&[MAX_NUMBER]));
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+
+use crate::card::Card;
+use crate::custom_error::AocError;
+
+// What to do with wins that would copy cards past the end of the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    // The puzzle says this never happens, silently drop those copies
+    #[default]
+    Lenient,
+    // Report the first card that would copy past the end
+    Strict,
+}
+
+// `aoc run --strict` reports wins past the end along with messy inputs
+impl From<aoc_harness::input::Mode> for Mode {
+    fn from(mode: aoc_harness::input::Mode) -> Self {
+        match mode {
+            aoc_harness::input::Mode::Fix => Mode::Lenient,
+            aoc_harness::input::Mode::Strict => Mode::Strict,
+        }
+    }
+}
+
+// The number of copies of every card once all the winnings are handed out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cascade {
+    pub numbers: Vec<u32>,
+    pub matches: Vec<u32>,
+    pub copies: Vec<u128>,
+}
+
+// Counters for the cascade, the u64 version is tried first and we only fall
+// back to u128 when it overflows
+trait Count: Copy {
+    const ONE: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn widen(self) -> u128;
+}
+
+impl Count for u64 {
+    const ONE: Self = 1;
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u64::checked_add(self, other)
+    }
+    fn widen(self) -> u128 {
+        self as u128
+    }
+}
+
+impl Count for u128 {
+    const ONE: Self = 1;
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u128::checked_add(self, other)
+    }
+    fn widen(self) -> u128 {
+        self
+    }
+}
+
+// Returns the index of the card whose copies overflowed
+fn copies<T: Count>(matches: &[u32]) -> Result<Vec<T>, usize> {
+    let mut copies = vec![T::ONE; matches.len()];
+    for i in 0..copies.len() {
+        let won = i + 1..(i + 1 + matches[i] as usize).min(copies.len());
+        for j in won {
+            copies[j] = copies[j].checked_add(copies[i]).ok_or(j)?;
+        }
+    }
+    Ok(copies)
+}
+
+impl Cascade {
+    #[tracing::instrument(skip(cards))]
+    pub fn new(cards: &[Card], mode: Mode) -> Result<Self, AocError> {
+        let numbers: Vec<u32> = cards.iter().map(|card| card.number).collect();
+        let matches: Vec<u32> = cards.iter().map(Card::matches).collect();
+
+        if mode == Mode::Strict {
+            let past_end = matches
+                .iter()
+                .enumerate()
+                .find(|(i, m)| i + **m as usize >= cards.len());
+            if let Some((i, m)) = past_end {
+                return Err(AocError::CopiesPastEnd {
+                    card: numbers[i],
+                    matches: *m,
+                    remaining: cards.len() - i - 1,
+                });
+            }
+        }
+
+        let copies = match copies::<u64>(&matches) {
+            Ok(copies) => copies.into_iter().map(Count::widen).collect(),
+            Err(_) => copies::<u128>(&matches).map_err(|i| {
+                AocError::CopyOverflow { card: numbers[i] }
+            })?,
+        };
+
+        Ok(Self { numbers, matches, copies })
+    }
+
+    pub fn total(&self) -> Result<u128, AocError> {
+        self.copies.iter().zip(&self.numbers).try_fold(
+            0u128,
+            |total, (copies, number)| {
+                total
+                    .checked_add(*copies)
+                    .ok_or(AocError::CopyOverflow { card: *number })
+            },
+        )
+    }
+}
+
+// A table with a row per card, e.g.
+//  card | matches | copies | wins
+//     1 |       4 |      1 | 2-5
+impl fmt::Display for Cascade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let copies_width = self
+            .copies
+            .iter()
+            .map(|c| c.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max("copies".len());
+        writeln!(f, "{:>5} | {:>7} | {:>copies_width$} | wins", "card", "matches", "copies")?;
+        writeln!(f, "{:-<5}-+-{:-<7}-+-{:-<copies_width$}-+-{:-<5}", "", "", "", "")?;
+        let last = self.numbers.len();
+        for (i, ((number, matches), copies)) in self
+            .numbers
+            .iter()
+            .zip(&self.matches)
+            .zip(&self.copies)
+            .enumerate()
+        {
+            let won = (i + 1)..(i + 1 + *matches as usize).min(last);
+            let wins = if won.is_empty() {
+                "-".to_string()
+            } else {
+                format!(
+                    "{}-{}",
+                    self.numbers[won.start],
+                    self.numbers[won.end - 1]
+                )
+            };
+            writeln!(f, "{number:>5} | {matches:>7} | {copies:>copies_width$} | {wins}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_histogram() -> miette::Result<()> {
+        let (_, cards) = parse_cards(INPUT).unwrap();
+        let cascade = Cascade::new(&cards, Mode::Strict)?;
+        assert_eq!(vec![1, 2, 4, 8, 14, 1], cascade.copies);
+        assert_eq!(vec![4, 2, 2, 1, 0, 0], cascade.matches);
+        assert_eq!(30, cascade.total()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_copies_past_end() -> miette::Result<()> {
+        let (_, cards) = parse_cards("Card 1: 1 2 | 1 3
+Card 2: 1 2 | 1 2").unwrap();
+        assert!(matches!(
+            Cascade::new(&cards, Mode::Strict),
+            Err(AocError::CopiesPastEnd { card: 2, matches: 2, remaining: 0 })
+        ));
+        // the copies that would fall off the table are dropped
+        let cascade = Cascade::new(&cards, Mode::Lenient)?;
+        assert_eq!(vec![1, 2], cascade.copies);
+        Ok(())
+    }
+
+    // Every card wins all the following ones, so card n has 2^(n-1) copies
+    fn doubling(count: usize) -> Vec<Card> {
+        (0..count)
+            .map(|i| {
+                let wins: Vec<u32> = (0..(count - i - 1) as u32).collect();
+                Card::new(i as u32 + 1, &wins, &wins).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_falls_back_to_u128() -> miette::Result<()> {
+        let cascade = Cascade::new(&doubling(100), Mode::Strict)?;
+        assert_eq!(1 << 99, cascade.copies[99]);
+        assert_eq!((1 << 100) - 1, cascade.total()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(matches!(
+            Cascade::new(&doubling(129), Mode::Strict),
+            Err(AocError::CopyOverflow { card: 129 })
+        ));
+    }
+
+    #[test]
+    fn test_table() -> miette::Result<()> {
+        let (_, cards) = parse_cards(INPUT).unwrap();
+        let cascade = Cascade::new(&cards, Mode::Lenient)?;
+        assert_eq!(
+            " card | matches | copies | wins
+------+---------+--------+------
+    1 |       4 |      1 | 2-5
+    2 |       2 |      2 | 3-4
+    3 |       2 |      4 | 4-5
+    4 |       1 |      8 | 5-5
+    5 |       0 |     14 | -
+    6 |       0 |      1 | -
+",
+            cascade.to_string()
+        );
+        Ok(())
+    }
+}
@@ -2,9 +2,39 @@
 use miette::Diagnostic;
 use thiserror::Error;
 
+use crate::validate::InvalidCards;
+
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
+
+    #[error("card {card} wins {matches} cards but only {remaining} cards follow it")]
+    #[diagnostic(code(aoc::copies_past_end))]
+    CopiesPastEnd { card: u32, matches: u32, remaining: usize },
+
+    #[error("the number of copies overflows a u128 at card {card}")]
+    #[diagnostic(code(aoc::copy_overflow))]
+    CopyOverflow { card: u32 },
+
+    #[error("card {card} has the number {number}, but numbers only go up to {max}", max = crate::card::MAX_NUMBER)]
+    #[diagnostic(code(aoc::number_too_large))]
+    NumberTooLarge { card: u32, number: u32 },
+
+    #[error("the total score overflows a u128 at card {card}")]
+    #[diagnostic(code(aoc::score_overflow))]
+    ScoreOverflow { card: u32 },
+
+    #[error("failed to parse cards: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InvalidCards(#[from] InvalidCards),
 }
\ No newline at end of file
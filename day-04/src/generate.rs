@@ -0,0 +1,66 @@
+use fastrand::Rng;
+
+// `size` cards with 10 winning numbers and 25 of mine, like the real input.
+// Most cards win nothing and no card wins more cards than follow it, so the
+// cascade stays well within a u128.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    for id in 1..=size {
+        let remaining = size - id;
+        let matches = match rng.u8(0..10) {
+            0..=6 => 0,
+            _ => rng.usize(1..=4),
+        }
+        .min(remaining);
+
+        let mut numbers: Vec<u32> = (1..=99).collect();
+        rng.shuffle(&mut numbers);
+        let winning = &numbers[..10];
+        let mut mine: Vec<u32> = winning[..matches]
+            .iter()
+            .chain(&numbers[10..35 - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut mine);
+
+        let list = |numbers: &[u32]| {
+            numbers.iter().map(|n| format!("{n:>2}")).collect::<Vec<_>>().join(" ")
+        };
+        input.push_str(&format!("Card {id:>3}: {} | {}\n", list(winning), list(&mine)));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated_input_solves() -> miette::Result<()> {
+        let input = generate(1000, 1);
+        assert_eq!(1000, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_never_copies_past_end() -> miette::Result<()> {
+        use crate::card::parse_cards;
+        use crate::cascade::{Cascade, Mode};
+        for seed in 0..20 {
+            let input = generate(30, seed);
+            let (_, cards) = parse_cards(&input).unwrap();
+            Cascade::new(&cards, Mode::Strict)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
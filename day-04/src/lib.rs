@@ -1,4 +1,17 @@
 pub mod custom_error;
 
+pub mod card;
+pub mod cascade;
+pub mod generate;
+pub mod parsed;
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod scale;
+pub mod validate;
+
+use aoc_harness::Puzzle;
+
+pub const PUZZLE: Puzzle<parsed::Parsed> = Puzzle {
+    parse: |input| Ok(parsed::parse(input)?),
+    parts: [part1::VARIANTS, part2::VARIANTS],
+};
\ No newline at end of file
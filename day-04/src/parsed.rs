@@ -0,0 +1,20 @@
+use nom::Finish;
+
+use crate::card::{parse_cards, Card};
+use crate::custom_error::AocError;
+use crate::validate::validate;
+
+// Part 1 scores the cards, part 2 wins copies of them
+pub type Parsed = Vec<Card>;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    validate(&input)?;
+    let (_, cards) = parse_cards(&input)
+        .finish()
+        .map_err(|e| AocError::ParseError(e.to_string()))?;
+    Ok(cards)
+}
\ No newline at end of file
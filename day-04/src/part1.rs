@@ -1,115 +1,77 @@
-use nom::bytes::complete::tag;
-use nom::character::complete::{digit1, line_ending, multispace0, multispace1};
-use nom::IResult;
-use nom::multi::separated_list1;
-use nom::sequence::{delimited, preceded, separated_pair};
+use crate::card::Card;
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
 
-#[derive(Debug)]
-#[derive(PartialEq)]
-#[derive(Clone)]
-struct Card {
-    number: u32,
-    winning: Vec<u32>,
-    mine: Vec<u32>,
-}
-impl Card {
-    fn my_winning_numbers(&self) -> Vec<&u32> {
-        let numbers = self.mine.iter().filter(|&i| self.winning.contains(i) ).collect();
-        numbers
-    }
-    fn score(&self) -> u32 {
-        let num_matches = self.my_winning_numbers().len() as u32;
-        let score = match num_matches.checked_sub(1) {
-            Some(n) => 2u32.pow(n),
-            None => 0,
-        };
-        score
+// Up to 128 matches, so 2^127 at most, `None` past a u128 all the same
+fn score(card: &Card) -> Option<u128> {
+    match card.matches().checked_sub(1) {
+        Some(n) => 2u128.checked_pow(n),
+        None => Some(0),
     }
 }
 
-#[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-
-    let (_, cards) = parse_cards(_input).expect("Failed to parse cards");
-    let score = cards.iter().map(|card| card.score()).sum::<u32>();
-    Ok(score.to_string())
+#[tracing::instrument(skip(cards))]
+pub fn solve(
+    cards: &[Card],
+) -> miette::Result<Answer, AocError> {
+    let score = cards.iter().try_fold(0u128, |sum, card| {
+        score(card)
+            .and_then(|score| sum.checked_add(score))
+            .ok_or(AocError::ScoreOverflow { card: card.number })
+    })?;
+    Ok(score.into())
 }
 
-fn parse_number_list(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, digits) = separated_list1(multispace1, digit1)(input)?;
-    Ok((input, digits.iter().map(|d| d.parse::<u32>().unwrap()).collect()))
-}
-
-// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-fn parse_card(input: &str) -> IResult<&str, Card> {
-    let (input, (card_number, (winning, mine))) = separated_pair(
-        preceded(tag("Card"), preceded(multispace1, digit1)), delimited(multispace0, tag(":"), multispace0),
-        separated_pair(parse_number_list, delimited(multispace0, tag("|"), multispace0), parse_number_list)
-    )(input)?;
-    let number = card_number.parse::<u32>().unwrap();
-    Ok((input, Card { number, winning, mine }))
+#[tracing::instrument(skip(_input))]
+pub fn process(
+    _input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(_input)?)
 }
 
-fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
-    let (input, cards) = separated_list1(line_ending, parse_card)(input)?;
-    Ok((input, cards))
-}
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::{parse_card, MAX_NUMBER};
     use rstest::rstest;
 
+    aoc_harness::example_tests!(process, "examples/part1/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part1/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part1/*.txt");
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!("13", process(input)?);
-        Ok(())
+    fn test_process_invalid() {
+        let result = process("Card 1: 1 128 | 1\nCard 3: 1 2 | 1");
+        assert!(matches!(result, Err(AocError::InvalidCards(invalid)) if invalid.problems.len() == 2));
     }
 
-        #[test]
-    fn test_parse_cards() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let result = parse_cards(input).expect("Failed to parse cards");
-        assert_eq!(result, ("", vec![
-            Card { number: 1, winning: vec![41, 48, 83, 86, 17], mine: vec![83, 86, 6, 31, 17, 9, 48, 53] },
-            Card { number: 2, winning: vec![13, 32, 20, 16, 61], mine: vec![61, 30, 68, 82, 17, 32, 24, 19] },
-            Card { number: 3, winning: vec![1, 21, 53, 59, 44], mine: vec![69, 82, 63, 72, 16, 21, 14, 1] },
-            Card { number: 4, winning: vec![41, 92, 73, 84, 69], mine: vec![59, 84, 76, 51, 58, 5, 54, 83] },
-            Card { number: 5, winning: vec![87, 83, 26, 28, 32], mine: vec![88, 30, 70, 12, 93, 22, 82, 36] },
-            Card { number: 6, winning: vec![31, 18, 13, 56, 72], mine: vec![74, 77, 10, 23, 35, 67, 36, 11] },
-        ]));
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 8)]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", 2)]
+    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", 2)]
+    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0)]
+    fn test_score(#[case] input: &str, #[case] expected: u128) -> miette::Result<()> {
+        let (_, card) = parse_card(input).unwrap();
+        assert_eq!(Some(expected), score(&card));
         Ok(())
     }
 
     #[test]
-    fn test_parse_number_list() -> miette::Result<()> {
-        let input = "41 48 83 86 17";
-        let (_, numbers) = parse_number_list(input).unwrap();
-        assert_eq!(vec![41, 48, 83, 86, 17], numbers);
-        Ok(())
-    }
-    #[rstest]
-    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", Card {number: 1, winning: vec![41, 48, 83, 86, 17], mine: vec![83, 86, 6, 31, 17, 9, 48, 53]}, 8)]
-    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", Card {number: 2, winning: vec![13, 32, 20, 16, 61], mine: vec![61, 30, 68, 82, 17, 32, 24, 19]}, 2)]
-    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", Card {number: 3, winning: vec![1, 21, 53, 59, 44], mine: vec![69, 82, 63, 72, 16, 21, 14, 1]}, 2)]
-    fn test_parse_card(#[case] input: &str, #[case] expected: Card, #[case] score: u32) -> miette::Result<()> {
-        let (_, card) = parse_card(input).unwrap();
-        assert_eq!(expected, card);
-        assert_eq!(score, card.score());
+    fn test_many_matches() -> miette::Result<()> {
+        let numbers: Vec<u32> = (1..=40).collect();
+        assert_eq!(Some(1 << 39), score(&Card::new(1, &numbers, &numbers).unwrap()));
+        let all: Vec<String> = (0..=MAX_NUMBER).map(|n| n.to_string()).collect();
+        let card = format!("{} | {}", all.join(" "), all.join(" "));
+        assert_eq!(1u128 << 127, process(&format!("Card 1: {card}"))?);
+        assert!(matches!(
+            process(&format!("Card 1: {card}\nCard 2: {card}")),
+            Err(AocError::ScoreOverflow { card: 2 })
+        ));
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,26 @@
+// The cards repeated `factor` times and renumbered so they stay in sequence.
+// The last cards of the input never win anything, so the copies of one
+// repetition don't spill into the next.
+pub fn scaled(input: &str, factor: usize) -> String {
+    let mut scaled = String::with_capacity((input.len() + 8) * factor);
+    let cards = input.lines().filter_map(|line| line.split_once(':'));
+    for (id, (_, numbers)) in (1..).zip(cards.cycle().take(factor * input.lines().count())) {
+        scaled.push_str(&format!("Card {id:>3}:{numbers}\n"));
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled() -> miette::Result<()> {
+        let input = "Card 1: 41 48 | 48 83\nCard 2: 13 32 | 61 30";
+        let scaled = scaled(input, 3);
+        assert!(scaled.starts_with("Card   1: 41 48 | 48 83\nCard   2: 13 32 | 61 30\nCard   3:"));
+        // still numbered in sequence, so it passes validation
+        assert_eq!(3, crate::part1::process(&scaled)?);
+        Ok(())
+    }
+}
\ No newline at end of file
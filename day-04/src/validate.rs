@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::card::MAX_NUMBER;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum List {
+    Winning,
+    Mine,
+}
+
+impl std::fmt::Display for List {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            List::Winning => write!(f, "winning"),
+            List::Mine => write!(f, "mine"),
+        }
+    }
+}
+
+#[derive(Error, Diagnostic, Debug, PartialEq)]
+pub enum Problem {
+    #[error("line is not a card")]
+    #[diagnostic(
+        code(aoc::malformed_card),
+        help("cards look like `Card 1: 41 48 83 | 83 86  6`")
+    )]
+    MalformedCard {
+        #[label("expected `Card <id>: <numbers> | <numbers>`")]
+        span: SourceSpan,
+    },
+
+    #[error("{text} is not a number")]
+    #[diagnostic(code(aoc::malformed_number))]
+    MalformedNumber {
+        text: String,
+        #[label("expected digits only")]
+        span: SourceSpan,
+    },
+
+    #[error("number {text} is out of range")]
+    #[diagnostic(code(aoc::number_out_of_range))]
+    NumberOutOfRange {
+        text: String,
+        #[label("must be at most {}", MAX_NUMBER)]
+        span: SourceSpan,
+    },
+
+    #[error("number {number} appears twice in the {list} list")]
+    #[diagnostic(code(aoc::duplicate_number))]
+    DuplicateNumber {
+        number: u32,
+        list: List,
+        #[label("duplicate")]
+        span: SourceSpan,
+        #[label("first seen here")]
+        first: SourceSpan,
+    },
+
+    #[error("expected card {expected}, found card {found}")]
+    #[diagnostic(code(aoc::card_out_of_sequence))]
+    CardOutOfSequence {
+        expected: u32,
+        found: u32,
+        #[label("cards should be numbered 1, 2, 3, ...")]
+        span: SourceSpan,
+    },
+
+    #[error("the {list} list has {found} numbers, the other cards have {expected}")]
+    #[diagnostic(code(aoc::list_length))]
+    ListLength {
+        list: List,
+        expected: usize,
+        found: usize,
+        #[label("{found} numbers")]
+        span: SourceSpan,
+    },
+}
+
+#[derive(Error, Diagnostic, Debug)]
+#[error("found {} problem(s) in the scratchcards", problems.len())]
+#[diagnostic(code(aoc::invalid_cards))]
+pub struct InvalidCards {
+    #[source_code]
+    pub input: String,
+    #[related]
+    pub problems: Vec<Problem>,
+}
+
+// A whitespace separated word and its offset in the whole input
+struct Token<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+fn tokens(text: &str, offset: usize) -> Vec<Token<'_>> {
+    text.split_whitespace()
+        .map(|word| Token {
+            text: word,
+            // `split_whitespace` hands out subslices of `text`
+            offset: offset + (word.as_ptr() as usize - text.as_ptr() as usize),
+        })
+        .collect()
+}
+
+fn span(token: &Token) -> SourceSpan {
+    (token.offset, token.text.len()).into()
+}
+
+struct Line<'a> {
+    id: Token<'a>,
+    lists: [(List, SourceSpan, Vec<Token<'a>>); 2],
+}
+
+fn list(kind: List, text: &str, offset: usize) -> (List, SourceSpan, Vec<Token<'_>>) {
+    let tokens = tokens(text, offset);
+    let span = match (tokens.first(), tokens.last()) {
+        (Some(first), Some(last)) => {
+            (first.offset, last.offset + last.text.len() - first.offset).into()
+        }
+        _ => (offset, text.len()).into(),
+    };
+    (kind, span, tokens)
+}
+
+// Card <id>: <numbers> | <numbers>
+fn split_line(line: &str, offset: usize) -> Option<Line<'_>> {
+    let rest = line.strip_prefix("Card")?;
+    let (id, numbers) = rest.split_once(':')?;
+    let mut id_tokens = tokens(id, offset + 4);
+    if id_tokens.len() != 1 || !id.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (winning, mine) = numbers.split_once('|')?;
+    let winning_offset = offset + 4 + id.len() + 1;
+    let mine_offset = winning_offset + winning.len() + 1;
+    Some(Line {
+        id: id_tokens.remove(0),
+        lists: [
+            list(List::Winning, winning, winning_offset),
+            list(List::Mine, mine, mine_offset),
+        ],
+    })
+}
+
+#[tracing::instrument(skip(input))]
+pub fn validate(input: &str) -> Result<(), InvalidCards> {
+    let mut problems = vec![];
+    let mut lines = vec![];
+
+    let mut offset = 0;
+    for raw in input.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        if !line.trim().is_empty() {
+            match split_line(line, offset) {
+                Some(parsed) => lines.push(parsed),
+                None => problems.push(Problem::MalformedCard { span: (offset, line.len()).into() }),
+            }
+        }
+        offset += raw.len();
+    }
+
+    // The most common list lengths are the ones every card should have
+    let mut shapes: HashMap<(usize, usize), usize> = HashMap::new();
+    for line in &lines {
+        *shapes.entry((line.lists[0].2.len(), line.lists[1].2.len())).or_default() += 1;
+    }
+    let expected_shape = shapes
+        .into_iter()
+        .max_by_key(|(shape, count)| (*count, std::cmp::Reverse(*shape)))
+        .map(|(shape, _)| shape);
+
+    for (expected_id, line) in (1..).zip(&lines) {
+        match line.id.text.parse::<u32>() {
+            Ok(id) if id == expected_id => {}
+            Ok(id) => problems.push(Problem::CardOutOfSequence {
+                expected: expected_id,
+                found: id,
+                span: span(&line.id),
+            }),
+            Err(_) => problems.push(Problem::MalformedCard { span: span(&line.id) }),
+        }
+
+        for (index, (list, list_span, numbers)) in line.lists.iter().enumerate() {
+            if let Some(shape) = expected_shape {
+                let expected = if index == 0 { shape.0 } else { shape.1 };
+                if numbers.len() != expected {
+                    problems.push(Problem::ListLength {
+                        list: *list,
+                        expected,
+                        found: numbers.len(),
+                        span: *list_span,
+                    });
+                }
+            }
+
+            let mut seen: HashMap<u32, &Token> = HashMap::new();
+            for token in numbers {
+                if !token.text.bytes().all(|b| b.is_ascii_digit()) {
+                    problems.push(Problem::MalformedNumber {
+                        text: token.text.to_string(),
+                        span: span(token),
+                    });
+                    continue;
+                }
+                // all digits, so the ones that don't parse are past u32::MAX
+                let number = match token.text.parse::<u32>() {
+                    Ok(number) if number <= MAX_NUMBER => number,
+                    _ => {
+                        problems.push(Problem::NumberOutOfRange {
+                            text: token.text.to_string(),
+                            span: span(token),
+                        });
+                        continue;
+                    }
+                };
+                if let Some(first) = seen.get(&number) {
+                    problems.push(Problem::DuplicateNumber {
+                        number,
+                        list: *list,
+                        span: span(token),
+                        first: span(first),
+                    });
+                } else {
+                    seen.insert(number, token);
+                }
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidCards { input: input.to_string(), problems })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::{GraphicalReportHandler, GraphicalTheme};
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    fn problems(input: &str) -> Vec<Problem> {
+        validate(input).map_or_else(|invalid| invalid.problems, |_| vec![])
+    }
+
+    #[test]
+    fn test_valid() {
+        assert!(validate(INPUT).is_ok());
+        assert!(validate(INPUT.trim_end()).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_number() {
+        let input = "Card 1: 41 48 41 | 1 2\nCard 2: 1 2 3 | 4 4";
+        assert_eq!(
+            vec![
+                Problem::DuplicateNumber { number: 41, list: List::Winning, span: (14, 2).into(), first: (8, 2).into() },
+                Problem::DuplicateNumber { number: 4, list: List::Mine, span: (41, 1).into(), first: (39, 1).into() },
+            ],
+            problems(input)
+        );
+    }
+
+    #[test]
+    fn test_out_of_sequence() {
+        let input = "Card 1: 1 | 2\nCard 3: 1 | 2\nCard 3: 1 | 2";
+        assert_eq!(
+            vec![
+                Problem::CardOutOfSequence { expected: 2, found: 3, span: (19, 1).into() },
+            ],
+            problems(input)
+        );
+    }
+
+    #[test]
+    fn test_list_length() {
+        let input = "Card 1: 1 2 | 3 4 5\nCard 2: 1 2 | 3 4\nCard 3: 1 2 | 3 4 5";
+        assert_eq!(
+            vec![
+                Problem::ListLength { list: List::Mine, expected: 3, found: 2, span: (34, 3).into() },
+            ],
+            problems(input)
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let input = "Card 1: 1 128 | 99999999999 4";
+        assert_eq!(
+            vec![
+                Problem::NumberOutOfRange { text: "128".to_string(), span: (10, 3).into() },
+                Problem::NumberOutOfRange { text: "99999999999".to_string(), span: (16, 11).into() },
+            ],
+            problems(input)
+        );
+    }
+
+    #[test]
+    fn test_malformed_number() {
+        let input = "Card 1: 1 4x | -3 +4 5";
+        assert_eq!(
+            vec![
+                Problem::MalformedNumber { text: "4x".to_string(), span: (10, 2).into() },
+                Problem::MalformedNumber { text: "-3".to_string(), span: (15, 2).into() },
+                Problem::MalformedNumber { text: "+4".to_string(), span: (18, 2).into() },
+            ],
+            problems(input)
+        );
+    }
+
+    #[test]
+    fn test_malformed() {
+        let input = "Card 1: 1 | 2\nGame 2: 1 | 2\nCard 3: 1 2";
+        assert_eq!(
+            vec![
+                Problem::MalformedCard { span: (14, 13).into() },
+                Problem::MalformedCard { span: (28, 11).into() },
+            ],
+            problems(input)
+        );
+    }
+
+    #[test]
+    fn test_report_points_at_line() {
+        let invalid = validate("Card 1: 5 5 | 1").unwrap_err();
+        let mut report = String::new();
+        GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+            .render_report(&mut report, &invalid)
+            .unwrap();
+        assert!(report.contains("number 5 appears twice in the winning list"));
+        assert!(report.contains("Card 1: 5 5 | 1"));
+        assert!(report.contains("first seen here"));
+    }
+}
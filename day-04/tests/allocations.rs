@@ -0,0 +1,23 @@
+// dhat sees every allocation in the process, so the budgets are checked from
+// their own test binary with dhat as the global allocator
+use std::path::{Path, PathBuf};
+
+use aoc_harness::{assert_allocations, Budget};
+use day_04::{part1, part2};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn example(part: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join(part).join("example.txt")
+}
+
+#[test]
+fn part1_allocations() -> miette::Result<()> {
+    assert_allocations(&example("part1"), part1::process, Budget { blocks: 99, peak_bytes: 4_080 })
+}
+
+#[test]
+fn part2_allocations() -> miette::Result<()> {
+    assert_allocations(&example("part2"), part2::process, Budget { blocks: 104, peak_bytes: 4_080 })
+}
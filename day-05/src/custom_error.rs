@@ -7,4 +7,27 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
+
+    #[error("failed to parse the almanac: {0}")]
+    #[diagnostic(
+        code(aoc::parse_error),
+        help("map rows need a destination, a start and a non-zero length that fit in a u64")
+    )]
+    ParseError(String),
+
+    #[error("seeds come in (start, length) pairs, but there are {0} numbers")]
+    #[diagnostic(code(aoc::odd_seeds))]
+    OddSeeds(usize),
+
+    #[error("the seed range starting at {start} with length {length} doesn't fit in a u64")]
+    #[diagnostic(code(aoc::seed_range_too_large))]
+    SeedRangeTooLarge { start: u64, length: u64 },
+
+    #[error("cancelled by the runner")]
+    #[diagnostic(code(aoc::cancelled))]
+    Cancelled,
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use fastrand::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// An almanac with `size` seeds (rounded up to whole pairs) and seven maps of
+// non-overlapping ranges over u32 numbers. The seed ranges of part 2 are kept
+// short, it still tries every seed.
+pub fn generate(size: usize, seed: u64) -> String {
+    const LIMIT: u64 = u32::MAX as u64;
+    let mut rng = Rng::with_seed(seed);
+
+    let seeds: Vec<String> = (0..size.div_ceil(2))
+        .flat_map(|_| [rng.u64(0..LIMIT - 1000), rng.u64(1..=1000)])
+        .map(|n| n.to_string())
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        input.push_str(&format!("\n{name} map:\n"));
+        // Cut the numbers into ranges, some of which get mapped somewhere else
+        let mut cuts: Vec<u64> = (0..rng.usize(10..=40) * 2).map(|_| rng.u64(0..LIMIT)).collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut rows = vec![];
+        for pair in cuts.chunks_exact(2) {
+            let (start, length) = (pair[0], pair[1] - pair[0]);
+            if length > 0 {
+                rows.push(format!("{} {start} {length}", rng.u64(0..=LIMIT - length)));
+            }
+        }
+        rng.shuffle(&mut rows);
+        input.push_str(&rows.join("\n"));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated_input_solves() -> miette::Result<()> {
+        let input = generate(10, 1);
+        assert!(input.starts_with("seeds: "));
+        part1::process(&input)?;
+        part2::process(&input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
@@ -0,0 +1,224 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::digit1;
+use nom::combinator::{map_res, verify};
+use nom::{Finish, IResult};
+use nom::multi::separated_list1;
+use nom::sequence::preceded;
+use crate::custom_error::AocError;
+
+#[derive(Debug)]
+pub(crate) struct Range {
+    pub(crate) min: u64,
+    pub(crate) max: u64,
+    pub(crate) destination: u64,
+}
+impl Range {
+    fn new(destination: u64, start: u64, length: u64) -> Self {
+        Self {
+            min: start,
+            max: start + length - 1,
+            destination,
+        }
+    }
+    pub(crate) fn resolve(&self, input: u64) -> Option<u64> {
+        if input >= self.min && input <= self.max {
+            Some(input - self.min + self.destination)
+        } else {
+            None
+        }
+    }
+}
+
+// A map contains multiple ranges
+#[derive(Debug)]
+pub(crate) struct Map {
+    pub(crate) ranges: Vec<Range>,
+}
+impl Map {
+    pub(crate) fn new(map: Vec<(u64, u64, u64)>) -> Self {
+        let ranges = map
+            .iter().map(
+            |&row| Range::new(row.0, row.1,row.2))
+            .collect();
+        Self {
+            ranges
+        }
+    }
+    pub(crate) fn resolve(&self, input: u64) -> u64 {
+        for range in &self.ranges {
+            let output = range.resolve(input);
+            match output {
+                Some(output) => return output,
+                None => continue,
+            }
+        }
+        input
+    }
+}
+#[derive(Debug)]
+// A sequence of maps
+pub struct Maps {
+    pub(crate) maps: Vec<Map>,
+}
+impl Maps {
+    // We walk through our maps and resolve the input through each map
+    pub(crate) fn resolve(&self, seed: u64) -> u64 {
+        let mut current = seed;
+        for map in &self.maps {
+            current = map.resolve(current);
+        }
+        current
+    }
+}
+// A row maps `length` numbers starting at `start` to the same amount of
+// numbers starting at `destination`, both ranges have to fit in a u64
+fn valid_row(row: &[u64]) -> bool {
+    match row {
+        [destination, start, length, ..] => {
+            *length > 0
+                && start.checked_add(length - 1).is_some()
+                && destination.checked_add(length - 1).is_some()
+        }
+        _ => false,
+    }
+}
+
+fn parse_map(input: &str) -> IResult<&str, Map> {
+    let (input, map) = separated_list1(
+        tag("\n"),
+        verify(
+            separated_list1(
+                tag(" "),
+                map_res(digit1, |digit_str: &str| digit_str.parse::<u64>()),
+            ),
+            |row: &Vec<u64>| valid_row(row),
+        ),
+    )(input)?;
+    // Only consider the first 3 columns, if we get extra data we discard it
+    let map: Vec<(u64, u64, u64)> = map
+        .iter()
+        .map(|row| (row[0], row[1], row[2]))
+        .collect();
+    Ok((input, Map::new(map)))
+}
+
+// Lazy parser, don't @ me
+fn parse_input(input: &str) -> IResult<&str, Almanac> {
+    let (input, seeds) = preceded(
+        tag("seeds: "),
+        separated_list1(
+            tag(" "),
+            map_res(digit1, |digit_str: &str| digit_str.parse::<u64>()),
+        ),
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, seed_to_soil_map) = preceded(
+        tag("seed-to-soil map:\n"),
+        parse_map
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, soil_to_fertilizer_map) = preceded(
+        tag("soil-to-fertilizer map:\n"),
+        parse_map
+
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, fertilizer_to_water_map) = preceded(
+        tag("fertilizer-to-water map:\n"),
+        parse_map
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, water_to_light_map) = preceded(
+        tag("water-to-light map:\n"),
+        parse_map
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, light_to_temperature_map) = preceded(
+        tag("light-to-temperature map:\n"),
+        parse_map
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, temperature_to_humidity_map) = preceded(
+        tag("temperature-to-humidity map:\n"),
+        parse_map
+    )(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, humidity_to_location_map) = preceded(
+        tag("humidity-to-location map:\n"),
+        parse_map
+    )(input)?;
+    let maps = Maps {
+        maps: vec![
+            seed_to_soil_map,
+            soil_to_fertilizer_map,
+            fertilizer_to_water_map,
+            water_to_light_map,
+            light_to_temperature_map,
+            temperature_to_humidity_map,
+            humidity_to_location_map,
+        ],
+    };
+    Ok((input, Almanac { seeds, maps }))
+}
+
+// Part 1 reads the seeds as single seeds, part 2 as (start, length) pairs
+#[derive(Debug)]
+pub struct Almanac {
+    pub(crate) seeds: Vec<u64>,
+    pub(crate) maps: Maps,
+}
+
+pub type Parsed = Almanac;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    let (_, almanac) = parse_input(&input)
+        .finish()
+        .map_err(|e| AocError::ParseError(e.to_string()))?;
+    Ok(almanac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_resolve() {
+        let range = Range {
+            min: 0,
+            max: 10,
+            destination: 100,
+        };
+        assert_eq!(Some(100), range.resolve(0));
+        assert_eq!(Some(101), range.resolve(1));
+        assert_eq!(Some(110), range.resolve(10));
+        assert_eq!(None, range.resolve(11));
+    }
+    #[test]
+    fn test_map() {
+        let map = Map::new(vec![
+            (50, 98, 2),
+            (52, 50, 48),
+        ]);
+        assert_eq!(10, map.resolve(10));
+        assert_eq!(49, map.resolve(49));
+        assert_eq!(52, map.resolve(50));
+        assert_eq!(99, map.resolve(97));
+        assert_eq!(50, map.resolve(98));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_maps() {
+        let maps = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-fertilizer map:\n0 15 37\n\nfertilizer-to-water map:\n49 53 8\n\nwater-to-light map:\n88 18 7\n\nlight-to-temperature map:\n45 77 23\n\ntemperature-to-humidity map:\n0 69 1\n\nhumidity-to-location map:\n60 56 37";
+        assert!(parse(maps).is_ok());
+        // zero length ranges and ranges running past u64::MAX
+        assert!(matches!(parse(&maps.replace("50 98 2", "50 98 0")), Err(AocError::ParseError(_))));
+        assert!(matches!(
+            parse(&maps.replace("50 98 2", "18446744073709551615 98 2")),
+            Err(AocError::ParseError(_))
+        ));
+    }
+}
@@ -1,240 +1,195 @@
-use nom::bytes::complete::tag;
-use nom::character::complete::digit1;
-use nom::combinator::map_res;
-use nom::IResult;
-use nom::multi::separated_list1;
-use nom::sequence::preceded;
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Almanac, Map, Maps, Parsed};
+use rayon::prelude::*;
 
-#[derive(Debug)]
-struct Range {
-    min: u64,
-    max: u64,
-    destination: u64,
-}
-impl Range {
-    fn new(destination: u64, start: u64, length: u64) -> Self {
-        Self {
-            min: start,
-            max: start + length - 1,
-            destination,
-        }
-    }
-    fn resolve(&self, input: u64) -> Option<u64> {
-        if (input >= self.min && input <= self.max) {
-            Some(input - self.min + self.destination)
-        } else {
-            None
-        }
-    }
-}
-
-// A map contains multiple ranges
-#[derive(Debug)]
-struct Map {
-    ranges: Vec<Range>,
-}
 impl Map {
-    fn new(map: Vec<(u64, u64, u64)>) -> Self {
-        let ranges = map
-            .iter().map(
-            |&row| Range::new(row.0, row.1,row.2))
-            .collect();
-        Self {
-            ranges
-        }
-    }
-    fn resolve(&self, input: u64) -> u64 {
+    // Maps every number in `min..=max` at once. The numbers a range matches
+    // stay contiguous, so the result is a handful of intervals rather than
+    // one number per seed. Like `resolve`, the first matching range wins.
+    fn resolve_interval(&self, min: u64, max: u64, output: &mut Vec<(u64, u64)>) {
+        let mut pending = vec![(min, max)];
         for range in &self.ranges {
-            let output = range.resolve(input);
-            match output {
-                Some(output) => return output,
-                None => continue,
+            let mut unmatched = vec![];
+            for (min, max) in pending {
+                let start = min.max(range.min);
+                let end = max.min(range.max);
+                if start > end {
+                    unmatched.push((min, max));
+                    continue;
+                }
+                output.push((start - range.min + range.destination, end - range.min + range.destination));
+                if min < start {
+                    unmatched.push((min, start - 1));
+                }
+                if end < max {
+                    unmatched.push((end + 1, max));
+                }
             }
+            pending = unmatched;
         }
-        input
+        // numbers no range matched map to themselves
+        output.extend(pending);
     }
 }
-#[derive(Debug)]
-// A sequence of maps
-struct Maps {
-    maps: Vec<Map>,
-}
+
 impl Maps {
-    // We walk through our maps and resolve the input through each map
-    fn resolve(&self, seed: u64) -> u64 {
-        let mut current = seed;
+    // The lowest location of any seed in `min..=max`
+    fn lowest(&self, min: u64, max: u64) -> u64 {
+        let mut intervals = vec![(min, max)];
         for map in &self.maps {
-            current = map.resolve(current);
+            let mut next = vec![];
+            for (min, max) in intervals {
+                map.resolve_interval(min, max, &mut next);
+            }
+            intervals = next;
         }
-        current
+        intervals.iter().map(|&(min, _)| min).min().unwrap_or(min)
     }
 }
-fn parse_map(input: &str) -> IResult<&str, Map> {
-    let (input, map) = separated_list1(
-        tag("\n"),
-        separated_list1(
-            tag(" "),
-            map_res(digit1, |digit_str: &str| digit_str.parse::<u64>()),
-        ),
-    )(input)?;
-    // Only consider the first 3 columns, if we get extra data we discard it
-    let map: Vec<(u64, u64, u64)> = map
-        .iter()
-        .map(|row| (row[0], row[1], row[2]))
-        .collect();
-    Ok((input, Map::new(map)))
+
+// The seeds as (start, length) pairs, every range has to fit in a u64
+fn seed_ranges(seeds: &[u64]) -> miette::Result<Vec<(u64, u64)>, AocError> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(AocError::OddSeeds(seeds.len()));
+    }
+    seeds
+        .chunks(2)
+        .map(|pair| match pair[0].checked_add(pair[1]) {
+            Some(_) => Ok((pair[0], pair[1])),
+            None => Err(AocError::SeedRangeTooLarge { start: pair[0], length: pair[1] }),
+        })
+        .collect()
 }
 
-// Lazy parser, don't @ me
-fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Maps)> {
-    let (input, seeds) = preceded(
-        tag("seeds: "),
-        separated_list1(
-            tag(" "),
-            map_res(digit1, |digit_str: &str| digit_str.parse::<u64>()),
-        ),
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, seed_to_soil_map) = preceded(
-        tag("seed-to-soil map:\n"),
-        parse_map
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, soil_to_fertilizer_map) = preceded(
-        tag("soil-to-fertilizer map:\n"),
-        parse_map
+#[tracing::instrument(skip(almanac))]
+pub fn solve(
+    almanac: &Almanac,
+) -> miette::Result<Answer, AocError> {
+    let Almanac { seeds, maps } = almanac;
+    let lowest = seed_ranges(seeds)?
+        .into_iter()
+        .filter(|&(_, length)| length > 0)
+        .map(|(start, length)| maps.lowest(start, start + length - 1))
+        .min()
+        .unwrap_or(0);
+    Ok(lowest.into())
+}
 
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, fertilizer_to_water_map) = preceded(
-        tag("fertilizer-to-water map:\n"),
-        parse_map
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, water_to_light_map) = preceded(
-        tag("water-to-light map:\n"),
-        parse_map
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, light_to_temperature_map) = preceded(
-        tag("light-to-temperature map:\n"),
-        parse_map
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, temperature_to_humidity_map) = preceded(
-        tag("temperature-to-humidity map:\n"),
-        parse_map
-    )(input)?;
-    let (input, _) = tag("\n\n")(input)?;
-    let (input, humidity_to_location_map) = preceded(
-        tag("humidity-to-location map:\n"),
-        parse_map
-    )(input)?;
-    let maps = Maps {
-        maps: vec![
-            seed_to_soil_map,
-            soil_to_fertilizer_map,
-            fertilizer_to_water_map,
-            water_to_light_map,
-            light_to_temperature_map,
-            temperature_to_humidity_map,
-            humidity_to_location_map,
-        ],
-    };
-    Ok((input, (seeds, maps)))
+// Resolves every single seed, takes minutes on the real input
+#[tracing::instrument(name = "solve", skip(almanac))]
+pub fn solve_naive(
+    almanac: &Almanac,
+) -> miette::Result<Answer, AocError> {
+    let Almanac { seeds, maps } = almanac;
+    let mut output = None;
+    for (start, length) in seed_ranges(seeds)? {
+        for i in 0..length {
+            // let the runner stop it when it's over its time limit
+            if i % 65_536 == 0 && aoc_harness::cancel::is_cancelled() {
+                return Err(AocError::Cancelled);
+            }
+            let solution = maps.resolve(start + i);
+            output = Some(output.map_or(solution, |output: u64| output.min(solution)));
+        }
+    }
+    Ok(output.unwrap_or(0).into())
 }
 
-#[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    let (_, (seeds, maps)) = parse_input(_input).expect("failed to parse input");
-    let outputs: Vec<u64> = seeds.iter().map(|&seed| maps.resolve(seed)).collect();
-    let pairs: Vec<(u64, u64)> = seeds
-        .chunks(2)
-        .map(|chunk| {
-            // You might want to add error handling if the chunks don't have exactly two elements
-            (chunk[0], chunk[1])
+// The naive brute force spread over all cores in chunks of seeds
+#[tracing::instrument(name = "solve", skip(almanac))]
+pub fn solve_parallel(
+    almanac: &Almanac,
+) -> miette::Result<Answer, AocError> {
+    const CHUNK: u64 = 1 << 16;
+    let Almanac { seeds, maps } = almanac;
+    // rayon's threads don't see the runner's token, check it explicitly
+    let token = aoc_harness::cancel::current();
+    let chunks: Vec<(u64, u64)> = seed_ranges(seeds)?
+        .into_iter()
+        .flat_map(|(start, length)| {
+            (0..length).step_by(CHUNK as usize).map(move |offset| (start + offset, (length - offset).min(CHUNK)))
         })
         .collect();
-    let mut output= 0;
-    for pair in pairs {
-        for i in 0..pair.1 {
-            let solution: u64 = maps.resolve(pair.0 + i);
-            if output == 0 || solution < output {
-                output = solution;
+    let lowest = chunks
+        .par_iter()
+        .map(|&(start, length)| {
+            if token.as_ref().is_some_and(|token| token.is_cancelled()) {
+                return Err(AocError::Cancelled);
             }
-        }
-    }
-    Ok(output.to_string())
+            Ok((start..start + length).map(|seed| maps.resolve(seed)).min())
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(0);
+    Ok(lowest.into())
+}
+
+#[tracing::instrument(skip(_input))]
+pub fn process(
+    _input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(_input)?)
 }
 
+// Every way of solving this part, the first is the default. `aoc run
+// --variant` picks another and `aoc verify` checks that they all agree.
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "interval", solve: |parsed| Ok(solve(parsed)?) },
+    Variant { name: "naive", solve: |parsed| Ok(solve_naive(parsed)?) },
+    Variant { name: "parallel", solve: |parsed| Ok(solve_parallel(parsed)?) },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    aoc_harness::example_tests!(process, "examples/part2/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part2/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part2/*.txt");
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    fn test_map_resolve_interval() {
+        // overlapping ranges, the first one wins like in `resolve`
+        let map = Map::new(vec![(100, 10, 5), (200, 12, 10)]);
+        let mut output = vec![];
+        map.resolve_interval(5, 30, &mut output);
+        output.sort();
+        assert_eq!(vec![(5, 9), (22, 30), (100, 104), (203, 209)], output);
+        for seed in 5..=30 {
+            let resolved = map.resolve(seed);
+            assert!(output.iter().any(|&(min, max)| (min..=max).contains(&resolved)));
+        }
+    }
 
-humidity-to-location map:
-60 56 37
-56 93 4
-";
-        assert_eq!("46", process(input)?);
+    #[test]
+    fn test_variants_agree_on_generated() -> miette::Result<()> {
+        for seed in 0..20 {
+            aoc_harness::assert_variants_agree(VARIANTS, &parse(&crate::generate::generate(20, seed))?)?;
+        }
         Ok(())
     }
+
     #[test]
-    fn test_range_resolve() {
-        let range = Range {
-            min: 0,
-            max: 10,
-            destination: 100,
-        };
-        assert_eq!(Some(100), range.resolve(0));
-        assert_eq!(Some(101), range.resolve(1));
-        assert_eq!(Some(110), range.resolve(10));
-        assert_eq!(None, range.resolve(11));
+    fn test_rejects_bad_seeds() {
+        let maps = "\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-fertilizer map:\n0 15 37\n\nfertilizer-to-water map:\n49 53 8\n\nwater-to-light map:\n88 18 7\n\nlight-to-temperature map:\n45 77 23\n\ntemperature-to-humidity map:\n0 69 1\n\nhumidity-to-location map:\n60 56 37";
+        for variant in VARIANTS {
+            let odd = parse(&format!("seeds: 79 14 55{maps}")).unwrap();
+            assert!(matches!((variant.solve)(&odd), Err(e) if e.to_string().contains("3 numbers")), "{variant}");
+            let too_large = parse(&format!("seeds: 18446744073709551615 2{maps}")).unwrap();
+            assert!(matches!((variant.solve)(&too_large), Err(e) if e.to_string().contains("doesn't fit")), "{variant}");
+        }
+        assert!(matches!(process(&format!("seeds: 79 14 55{maps}")), Err(AocError::OddSeeds(3))));
     }
+
     #[test]
-    fn test_map() {
-        let map = Map::new(vec![
-            (50, 98, 2),
-            (52, 50, 48),
-        ]);
-        assert_eq!(10, map.resolve(10));
-        assert_eq!(49, map.resolve(49));
-        assert_eq!(52, map.resolve(50));
-        assert_eq!(99, map.resolve(97));
-        assert_eq!(50, map.resolve(98));
+    fn test_solve_stops_when_cancelled() -> miette::Result<()> {
+        let almanac = parse(include_str!("../examples/part2/example.txt").split_once("---\n").unwrap().1)?;
+        let token = aoc_harness::cancel::CancellationToken::new();
+        token.cancel();
+        let result = aoc_harness::cancel::with_token(&token, || solve_naive(&almanac));
+        assert!(matches!(result, Err(AocError::Cancelled)));
+        Ok(())
     }
 }
\ No newline at end of file
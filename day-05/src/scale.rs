@@ -0,0 +1,18 @@
+// The seeds repeated `factor` times, the maps stay the same.
+// Only useful for part 1, part 2 would brute force `factor` times as many seeds.
+pub fn scaled(input: &str, factor: usize) -> String {
+    let (seeds_line, maps) = input.split_once('\n').unwrap_or((input, ""));
+    let seeds = seeds_line.strip_prefix("seeds:").unwrap_or(seeds_line);
+    format!("seeds:{}\n{maps}", seeds.repeat(factor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2";
+        assert_eq!("seeds: 79 14 79 14 79 14\n\nseed-to-soil map:\n50 98 2", scaled(input, 3));
+    }
+}
\ No newline at end of file
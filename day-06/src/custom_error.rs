@@ -7,4 +7,23 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Input(#[from] aoc_harness::input::NotNormalised),
+
+    #[error("failed to parse races: {0}")]
+    #[diagnostic(
+        code(aoc::parse_error),
+        help("expected a `Time:` line and a `Distance:` line with the same number of columns")
+    )]
+    ParseError(String),
+
+    #[error("the Time: row has {times} columns but the Distance: row has {distances}")]
+    #[diagnostic(code(aoc::column_mismatch))]
+    ColumnMismatch { times: usize, distances: usize },
+
+    #[error("{row} `{digits}` does not fit in a u128")]
+    #[diagnostic(code(aoc::number_too_large))]
+    NumberTooLarge { row: &'static str, digits: String },
 }
\ No newline at end of file
@@ -0,0 +1,42 @@
+use fastrand::Rng;
+
+// `size` races that can all be won. Part 2 joins the columns into a single
+// race, which only fits in a u128 for up to nine races.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let (mut times, mut distances) = (String::new(), String::new());
+    for _ in 0..size {
+        let time = rng.u64(7..=99);
+        // holding for half the time goes the furthest
+        let best = (time / 2) * (time - time / 2);
+        let distance = rng.u64(0..best);
+        let width = distance.to_string().len().max(2);
+        times.push_str(&format!("  {time:>width$}"));
+        distances.push_str(&format!("  {distance:>width$}"));
+    }
+    format!("Time:    {times}\nDistance:{distances}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::race::parse_sheet;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated_input_solves() -> miette::Result<()> {
+        let input = generate(9, 1);
+        let races = parse_sheet(&input)?.races()?;
+        assert_eq!(9, races.len());
+        assert!(races.iter().all(|race| race.ways_to_win() > 0));
+        part1::process(&input)?;
+        part2::process(&input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(20, 7), generate(20, 7));
+        assert_ne!(generate(20, 7), generate(20, 8));
+    }
+}
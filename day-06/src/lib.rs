@@ -1,4 +1,15 @@
 pub mod custom_error;
 
+pub mod generate;
+pub mod parsed;
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod race;
+pub mod scale;
+
+use aoc_harness::Puzzle;
+
+pub const PUZZLE: Puzzle<parsed::Parsed> = Puzzle {
+    parse: |input| Ok(parsed::parse(input)?),
+    parts: [part1::VARIANTS, part2::VARIANTS],
+};
\ No newline at end of file
@@ -0,0 +1,13 @@
+use crate::custom_error::AocError;
+use crate::race::{parse_sheet, Sheet};
+
+// Part 1 reads the sheet's columns as races, part 2 reads it as one race
+pub type Parsed = Sheet;
+
+#[tracing::instrument(skip(input))]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Parsed, AocError> {
+    let input = aoc_harness::input::normalise(input)?;
+    parse_sheet(&input)
+}
\ No newline at end of file
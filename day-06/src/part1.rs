@@ -1,21 +1,52 @@
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
+use crate::race::Sheet;
+use num_bigint::BigInt;
 
-#[tracing::instrument]
+#[tracing::instrument(skip(sheet))]
+pub fn solve(
+    sheet: &Sheet,
+) -> miette::Result<Answer, AocError> {
+    // each race fits in a u128, their product doesn't have to
+    let result = sheet
+        .races()?
+        .iter()
+        .map(|race| BigInt::from(race.ways_to_win()))
+        .product::<BigInt>();
+    Ok(result.into())
+}
+
+#[tracing::instrument(skip(input))]
 pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+    input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(input)?)
 }
 
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    aoc_harness::example_tests!(process, "examples/part1/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part1/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part1/*.txt");
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+    fn test_product_past_u128() -> miette::Result<()> {
+        let time = u64::MAX;
+        let answer = process(&format!("Time: {time} {time} {time}\nDistance: 0 0 0"))?;
+        // every hold but 0 and `time` wins
+        assert_eq!(BigInt::from(time - 1).pow(3), answer.to_bigint().unwrap());
         Ok(())
     }
+
+    #[test]
+    fn test_process_empty_input() {
+        assert!(matches!(process(""), Err(AocError::ParseError(_))));
+    }
 }
\ No newline at end of file
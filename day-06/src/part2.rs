@@ -1,21 +1,36 @@
 use crate::custom_error::AocError;
+use aoc_harness::{Answer, Variant};
+use crate::parsed::{parse, Parsed};
+use crate::race::Sheet;
 
-#[tracing::instrument]
+#[tracing::instrument(skip(sheet))]
+pub fn solve(
+    sheet: &Sheet,
+) -> miette::Result<Answer, AocError> {
+    Ok(sheet.kerned_race()?.ways_to_win().into())
+}
+
+#[tracing::instrument(skip(input))]
 pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+    input: &str,
+) -> miette::Result<Answer, AocError> {
+    solve(&parse(input)?)
 }
 
+pub const VARIANTS: &[Variant<Parsed>] = &[
+    Variant { name: "default", solve: |parsed| Ok(solve(parsed)?) },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    aoc_harness::example_tests!(process, "examples/part2/*.txt");
+    aoc_harness::normalisation_tests!(process, "examples/part2/*.txt");
+    aoc_harness::variant_tests!(parse, VARIANTS, "examples/part2/*.txt");
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
-        Ok(())
+    fn test_process_empty_input() {
+        assert!(matches!(process(""), Err(AocError::ParseError(_))));
     }
 }
\ No newline at end of file
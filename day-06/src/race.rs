@@ -0,0 +1,238 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, line_ending, space0, space1};
+use nom::combinator::opt;
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, terminated, tuple};
+use nom::{Finish, IResult};
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u128,
+    pub distance: u128,
+}
+
+impl Race {
+    fn beats(&self, hold: u128) -> bool {
+        // A product too big for a u128 is certainly bigger than the record
+        hold.checked_mul(self.time - hold)
+            .is_none_or(|distance| distance > self.distance)
+    }
+
+    // Holding the button for `hold` ms makes the boat travel hold * (time - hold),
+    // so we win for every integer strictly between the roots of
+    // hold^2 - time * hold + distance = 0.
+    // Everything stays in integers so part 2's big numbers don't lose precision.
+    pub fn ways_to_win(&self) -> u128 {
+        let half = self.time / 2;
+        if !self.beats(half) {
+            return 0;
+        }
+        let lowest = match self.time.checked_mul(self.time) {
+            Some(square) => {
+                // `beats(half)` means time^2 > 4 * distance, so this can't underflow.
+                // The lower root is (time - sqrt(discriminant)) / 2, isqrt rounds down
+                // so this is at most one step away from the first winning hold time.
+                let discriminant = square - 4 * self.distance;
+                let mut lowest = (self.time - discriminant.isqrt()) / 2;
+                while !self.beats(lowest) {
+                    lowest += 1;
+                }
+                while lowest > 0 && self.beats(lowest - 1) {
+                    lowest -= 1;
+                }
+                lowest
+            }
+            // time^2 doesn't fit in a u128, binary search for the first winning
+            // hold time instead
+            None => {
+                let (mut low, mut high) = (0, half);
+                while low < high {
+                    let middle = low + (high - low) / 2;
+                    if self.beats(middle) {
+                        high = middle;
+                    } else {
+                        low = middle + 1;
+                    }
+                }
+                low
+            }
+        };
+        // The winning hold times are symmetric around time / 2, and holding for
+        // 0 ms never wins so this can't overflow
+        self.time - 2 * lowest + 1
+    }
+
+    // Try every hold time, only used to check `ways_to_win`
+    pub fn ways_to_win_brute_force(&self) -> u128 {
+        (0..=self.time)
+            .filter(|hold| self.beats(*hold))
+            .count() as u128
+    }
+}
+
+// Time:      7  15   30
+fn parse_row<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<&'a str>> {
+    preceded(tuple((tag(name), space1)), terminated(separated_list1(space1, digit1), space0))
+}
+
+fn parse_rows(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    let (input, times) = terminated(parse_row("Time:"), line_ending)(input)?;
+    let (input, distances) = terminated(parse_row("Distance:"), opt(line_ending))(input)?;
+    Ok((input, (times, distances)))
+}
+
+// The digits of both rows, they can be read column by column or, since the
+// spaces between them are just bad kerning, as a single race
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    times: Vec<String>,
+    distances: Vec<String>,
+}
+
+#[tracing::instrument(skip(input))]
+pub fn parse_sheet(input: &str) -> Result<Sheet, AocError> {
+    let (_, (times, distances)) = parse_rows(input)
+        .finish()
+        .map_err(|e| AocError::ParseError(e.to_string()))?;
+    if times.len() != distances.len() {
+        return Err(AocError::ColumnMismatch {
+            times: times.len(),
+            distances: distances.len(),
+        });
+    }
+    let owned = |row: Vec<&str>| row.into_iter().map(String::from).collect();
+    Ok(Sheet { times: owned(times), distances: owned(distances) })
+}
+
+fn number(row: &'static str, digits: &str) -> Result<u128, AocError> {
+    digits.parse().map_err(|_| AocError::NumberTooLarge {
+        row,
+        digits: digits.to_string(),
+    })
+}
+
+impl Sheet {
+    // Every column is its own race
+    pub fn races(&self) -> Result<Vec<Race>, AocError> {
+        self.times
+            .iter()
+            .zip(&self.distances)
+            .map(|(time, distance)| {
+                Ok(Race {
+                    time: number("time", time)?,
+                    distance: number("distance", distance)?,
+                })
+            })
+            .collect()
+    }
+
+    // All the digits of a row make up a single number
+    pub fn kerned_race(&self) -> Result<Race, AocError> {
+        Ok(Race {
+            time: number("time", &self.times.concat())?,
+            distance: number("distance", &self.distances.concat())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_races() -> miette::Result<()> {
+        let sheet = parse_sheet(INPUT)?;
+        assert_eq!(
+            vec![
+                Race { time: 7, distance: 9 },
+                Race { time: 15, distance: 40 },
+                Race { time: 30, distance: 200 },
+            ],
+            sheet.races()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_kerned_race() -> miette::Result<()> {
+        let sheet = parse_sheet(INPUT)?;
+        assert_eq!(Race { time: 71530, distance: 940200 }, sheet.kerned_race()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_mismatched_columns() {
+        assert!(matches!(
+            parse_sheet("Time: 7 15\nDistance: 9"),
+            Err(AocError::ColumnMismatch { times: 2, distances: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_kerned_overflow() -> miette::Result<()> {
+        // 40 digits once the columns are joined, u128::MAX has 39
+        let input = "Time: 12345678901234567890 12345678901234567890
+Distance: 1 2";
+        let sheet = parse_sheet(input)?;
+        assert_eq!(2, sheet.races()?.len());
+        assert!(matches!(
+            sheet.kerned_race(),
+            Err(AocError::NumberTooLarge { row: "time", .. })
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Race { time: 7, distance: 9 }, 4)]
+    #[case(Race { time: 15, distance: 40 }, 8)]
+    // the roots are exactly 10 and 20, which only tie the record
+    #[case(Race { time: 30, distance: 200 }, 9)]
+    #[case(Race { time: 71530, distance: 940200 }, 71503)]
+    #[case(Race { time: 0, distance: 0 }, 0)]
+    #[case(Race { time: 1, distance: 0 }, 0)]
+    #[case(Race { time: 2, distance: 0 }, 1)]
+    #[case(Race { time: 4, distance: 4 }, 0)]
+    #[case(Race { time: 10, distance: 1000 }, 0)]
+    fn test_ways_to_win(#[case] race: Race, #[case] expected: u128) {
+        assert_eq!(expected, race.ways_to_win());
+        assert_eq!(expected, race.ways_to_win_brute_force());
+    }
+
+    #[test]
+    fn test_ways_to_win_large() {
+        // big enough that f64 can't hold time^2 exactly
+        let race = Race { time: 4_000_000_001, distance: 4_000_000_001_999_999_999 };
+        assert_eq!(2, race.ways_to_win());
+        let race = Race { time: 4_000_000_000, distance: 3_999_999_999_999_999_999 };
+        assert_eq!(1, race.ways_to_win());
+        let race = Race { time: 4_000_000_000, distance: 4_000_000_000_000_000_000 };
+        assert_eq!(0, race.ways_to_win());
+    }
+
+    #[test]
+    fn test_ways_to_win_time_squared_overflows() {
+        // time^2 doesn't fit in a u128, these take the binary search
+        let race = Race { time: u128::MAX, distance: 0 };
+        assert_eq!(u128::MAX - 1, race.ways_to_win());
+        let race = Race { time: (1 << 64) + 12345, distance: 1 << 126 };
+        assert_eq!(674_870_440_292, race.ways_to_win());
+        // the products near half the time overflow too
+        let race = Race { time: 1 << 70, distance: u128::MAX };
+        assert_eq!(1_180_015_019_158_858_076_139, race.ways_to_win());
+    }
+
+    proptest! {
+        #[test]
+        fn closed_form_matches_brute_force(time in 0u128..2000, distance in 0u128..1_000_001) {
+            let race = Race { time, distance };
+            prop_assert_eq!(race.ways_to_win_brute_force(), race.ways_to_win());
+        }
+    }
+}
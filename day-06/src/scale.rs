@@ -0,0 +1,24 @@
+// Every column repeated `factor` times.
+// Only useful for part 1, the kerned race of part 2 would no longer fit in a u128.
+pub fn scaled(input: &str, factor: usize) -> String {
+    input
+        .lines()
+        .map(|line| match line.split_once(':') {
+            Some((name, columns)) => format!("{name}:{}\n", columns.repeat(factor)),
+            None => format!("{line}\n"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled() -> miette::Result<()> {
+        let input = "Time:      7  15\nDistance:  9  40";
+        assert_eq!(32, crate::part1::process(&scaled(input, 1))?);
+        assert_eq!((32u128).pow(3), crate::part1::process(&scaled(input, 3))?);
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+#![no_main]
+
+use aoc_fuzz::day_04::{differential, MAX_SIZE};
+use libfuzzer_sys::fuzz_target;
+
+// (size, seed) of a generated input
+fuzz_target!(|case: (u8, u64)| differential(1 + case.0 as usize % (MAX_SIZE - 1), case.1));